@@ -1,34 +1,83 @@
 #![allow(unused_imports)]
 use super::{
-    config::Config,
-    errors::Error,
+    config::{AffinityStrategy, Config, split_args},
+    errors::{Error, ValidationWarning},
+    filter::BenchmarkFilter,
+    html,
     langs::BenchmarkLanguage,
-    paths::{PLOTS_PATH, RAW_PATH, SUITE_PATH, bin_path_aarch, bin_path_x86},
+    paths::{PLOTS_PATH, Paths, RAW_PATH, SUITE_PATH},
+    report::{CompileOutput, CompileWarning},
+    results::{
+        BinaryInfo, InstructionCountResult, Metadata, ResultDiff, RunOutcome, RunTimeEstimate,
+        StraceResult, TimingNoiseResult, WelchResult,
+    },
+    stats::{bootstrap_mean_ci, geometric_mean, mean, variance, welch_t_test},
+    svg,
+    tools::{
+        ProcessorInfo, available_parallelism, hyperfine_version, on_path, parse_version,
+        shell_quote,
+    },
 };
 use std::{
-    env,
-    fs::{create_dir_all, read_dir, copy, rename},
-    path::PathBuf,
+    collections::HashMap,
+    env, fmt,
+    fs::{File, copy, create_dir_all, metadata, read_dir, remove_file, rename, write},
+    hash::{DefaultHasher, Hash, Hasher},
+    path::{Path, PathBuf},
     process::Command,
     str,
+    sync::{
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
+/// Linux limit for a single argument (MAX_ARG_STRLEN), hyperfine gets every command as one
+const MAX_ARG_STRLEN: usize = 128 * 1024;
+
+/// Lowercase stderr fragments runtimes print when an allocation fails
+const OOM_MARKERS: [&str; 5] = [
+    "out of memory",
+    "memory allocation",
+    "cannot allocate memory",
+    "failed to allocate",
+    "allocation failed",
+];
+
+/// Scenario label (if any) and values after the command column of a result csv row
+type ResultRow<'a> = (Option<&'a str>, Vec<f64>);
+
 pub struct Benchmark {
+    /// Name used for binaries and result files
     pub name: String,
+    /// Name of the source and config files in `base_path`, usually the same as `name`
+    pub source_name: String,
     pub base_path: PathBuf,
     pub languages: Vec<BenchmarkLanguage>,
     pub config: Config,
+    /// Where binaries are written
+    pub paths: Paths,
 }
 
 impl Benchmark {
     pub fn new(name: &str, exclude_lang: &[BenchmarkLanguage]) -> Result<Benchmark, Error> {
-        let base_path = PathBuf::from(SUITE_PATH).join(name);
+        Benchmark::new_in(Path::new(SUITE_PATH), name, exclude_lang)
+    }
+
+    pub fn new_in(
+        suite_path: &Path,
+        name: &str,
+        exclude_lang: &[BenchmarkLanguage],
+    ) -> Result<Benchmark, Error> {
+        let base_path = suite_path.join(name);
         let mut config_path = base_path.clone().join(name);
         config_path.set_extension("args");
         let config = Config::from_file(config_path);
 
         let dir_contents = read_dir(&base_path).map_err(|err| Error::read_dir(&base_path, err))?;
         let mut languages = vec![];
+        let mut sources: Vec<(BenchmarkLanguage, PathBuf)> = vec![];
         for file in dir_contents {
             let file_path = file
                 .map_err(|_| Error::path_access(&base_path, "Read file path"))?
@@ -44,43 +93,458 @@ impl Benchmark {
                 continue;
             }
 
-            if let Some(lang) = BenchmarkLanguage::from_ext(ext) {
-                if !exclude_lang.contains(&lang) {
-                    languages.push(lang);
-                }
+            let Some(lang) = BenchmarkLanguage::from_ext(ext) else {
+                continue;
+            };
+            if let Some((_, path_a)) = sources.iter().find(|(other, _)| *other == lang) {
+                return Err(Error::duplicate_source(name, path_a, &file_path));
+            }
+            sources.push((lang, file_path.clone()));
+            if !exclude_lang.contains(&lang) {
+                languages.push(lang);
             }
         }
         Ok(Benchmark {
             name: name.to_owned(),
+            source_name: name.to_owned(),
             base_path,
             languages,
             config,
+            paths: Paths::default(),
         })
     }
 
-    pub fn bin_path(&self, lang: &BenchmarkLanguage) -> Result<PathBuf, Error> {
+    /// Copy of this benchmark whose binaries and result files are named `<prefix><name>`,
+    /// so the same benchmark can be run with several configs without overwriting results
+    /// The sources are still read from `base_path`
+    pub fn with_name_prefix(&self, prefix: &str) -> Benchmark {
+        Benchmark {
+            name: format!("{prefix}{}", self.name),
+            source_name: self.source_name.clone(),
+            base_path: self.base_path.clone(),
+            languages: self.languages.clone(),
+            config: self.config.clone(),
+            paths: self.paths.clone(),
+        }
+    }
+
+    /// Languages of this benchmark, sorted by their full name
+    /// ```
+    /// # use lib::{benchmark::Benchmark, config::Config, langs::BenchmarkLanguage, paths::Paths};
+    /// let bench = Benchmark {
+    ///     name: "Sorted".to_owned(),
+    ///     source_name: "Sorted".to_owned(),
+    ///     base_path: "suite/Sorted".into(),
+    ///     languages: vec![BenchmarkLanguage::Rust, BenchmarkLanguage::Koka, BenchmarkLanguage::OCaml],
+    ///     config: Config::default(),
+    ///     paths: Paths::default(),
+    /// };
+    /// assert_eq!(
+    ///     bench.languages_sorted(),
+    ///     [BenchmarkLanguage::Koka, BenchmarkLanguage::OCaml, BenchmarkLanguage::Rust]
+    /// );
+    /// ```
+    pub fn languages_sorted(&self) -> Vec<BenchmarkLanguage> {
+        let mut languages = self.languages.clone();
+        languages.sort_by_key(|lang| lang.to_string().to_lowercase());
+        languages
+    }
+
+    /// Comma-separated full names of the languages, e.g. `"Koka, MoonBit, OCaml"`
+    pub fn list_languages(&self) -> String {
+        self.languages_sorted()
+            .iter()
+            .map(|lang| lang.to_string())
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    pub fn config_path(&self) -> PathBuf {
+        let mut config_path = self.base_path.join(&self.source_name);
+        config_path.set_extension("args");
+        config_path
+    }
+
+    /// Checks the benchmark directory before compiling
+    /// Only a missing benchmark directory is an error, everything else results in warnings
+    pub fn validate(&self) -> Result<Vec<ValidationWarning>, Error> {
+        if !self.base_path.is_dir() {
+            return Err(Error::path_access(&self.base_path, "benchmark directory"));
+        }
+
+        let mut warnings = vec![];
+        let config_path = self.config_path();
+        if !config_path.exists() {
+            warnings.push(ValidationWarning::MissingArgs {
+                bench: self.name.clone(),
+                path: config_path,
+            });
+        }
+        if self.languages.is_empty() {
+            warnings.push(ValidationWarning::NoLanguages {
+                bench: self.name.clone(),
+            });
+        }
+        if self.config.expected.is_empty() {
+            warnings.push(ValidationWarning::MissingExpected {
+                bench: self.name.clone(),
+            });
+        }
+        for lang in self.languages.iter() {
+            for tool in lang.required_tools() {
+                if !on_path(tool) {
+                    warnings.push(ValidationWarning::MissingTool {
+                        bench: self.name.clone(),
+                        lang: *lang,
+                        tool: tool.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(warnings)
+    }
+
+    /// Validates all benchmarks, printing and returning all warnings
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn validate_all(benchmarks: &[Benchmark]) -> Result<Vec<ValidationWarning>, Error> {
+        let mut warnings = vec![];
+        for benchmark in benchmarks {
+            for warning in benchmark.validate()? {
+                println!("Warning: {warning}");
+                warnings.push(warning);
+            }
+        }
+        Ok(warnings)
+    }
+
+    /// JSON Schema of the `.args` config format, for editor completion and validation
+    #[cfg(feature = "serde")]
+    pub fn export_json_schema() -> serde_json::Value {
+        let string_list = serde_json::json!({ "type": "array", "items": { "type": "string" } });
+        let command = serde_json::json!({ "type": "string" });
+        let core = serde_json::json!({ "type": "integer", "minimum": 0 });
+        serde_json::json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "Benchmark config (.args)",
+            "type": "object",
+            "required": ["args", "runs", "test_args", "expected", "suite"],
+            "properties": {
+                "args": string_list,
+                "runs": { "type": "integer", "minimum": 1 },
+                "warmup": { "type": "integer", "minimum": 0 },
+                "heap_size": { "type": "integer", "minimum": 0 },
+                "test_args": string_list,
+                "expected": { "type": "string" },
+                "suite": { "type": "string" },
+                "scenario": {
+                    "type": "object",
+                    "additionalProperties": string_list,
+                },
+                "strict": { "type": "boolean" },
+                "tags": string_list,
+                "show_output": { "type": "boolean" },
+                "c_flags": string_list,
+                "cxx_flags": string_list,
+                "cleanup_cmd": command,
+                "setup_cmd": command,
+                "teardown_cmd": command,
+                "affinity": {
+                    "type": "string",
+                    "pattern": "^(none|all|all_available|single_core [0-9]+|cores [0-9]+(,[0-9]+)*)$",
+                },
+                "hyperfine_affinity": core,
+                "reproducible_mode": { "type": "boolean" },
+                "pinned_seed": { "type": "integer", "minimum": 0 },
+                "stdin_file": { "type": "string" },
+                "flamegraph": { "type": "boolean" },
+                "archive": { "type": "boolean" },
+                "perf_stat": { "type": "boolean" },
+                "env_passthrough": string_list,
+                "print_summary": { "type": "boolean" },
+                "set_malloc_arena_max": { "type": "boolean" },
+                "build_mode": { "enum": ["release", "debug"] },
+            },
+            "additionalProperties": false,
+        })
+    }
+
+    /// Source file of `lang`, Koka sources have to be lowercase
+    pub fn source_path(&self, lang: &BenchmarkLanguage) -> PathBuf {
+        let name = if *lang == BenchmarkLanguage::Koka {
+            self.source_name.to_lowercase()
+        } else {
+            self.source_name.clone()
+        };
+        let mut source_path = self.base_path.join(name);
+        source_path.set_extension(lang.ext());
+        source_path
+    }
+
+    /// Encoding of the source file of `lang`: `"UTF-8"`, `"UTF-8-BOM"` or `"binary"`
+    pub fn source_encoding(&self, lang: &BenchmarkLanguage) -> Result<&'static str, Error> {
+        let source_path = self.source_path(lang);
+        let contents = std::fs::read(&source_path)
+            .map_err(|err| Error::file_access(&source_path, "read source", err))?;
+        let (bom, text) = match contents.strip_prefix(b"\xEF\xBB\xBF") {
+            Some(text) => (true, text),
+            None => (false, contents.as_slice()),
+        };
+        Ok(match (bom, str::from_utf8(text).is_ok()) {
+            (_, false) => "binary",
+            (true, true) => "UTF-8-BOM",
+            (false, true) => "UTF-8",
+        })
+    }
+
+    /// SPDX license identifier from the first 5 lines of the source file of `lang`,
+    /// e.g. `MIT` for `// SPDX-License-Identifier: MIT`
+    pub fn check_source_license(&self, lang: &BenchmarkLanguage) -> Result<Option<String>, Error> {
+        let source_path = self.source_path(lang);
+        let contents = std::fs::read_to_string(&source_path)
+            .map_err(|err| Error::file_access(&source_path, "read source", err))?;
+        Ok(contents.lines().take(5).find_map(|line| {
+            let (_, license) = line.split_once("SPDX-License-Identifier:")?;
+            // drop the closing marker of block comments, e.g. `(* ... *)` or `{- ... -}`
+            let license = license
+                .trim()
+                .trim_end_matches(['*', ')', '-', '}', '/'])
+                .trim();
+            (!license.is_empty()).then(|| license.to_owned())
+        }))
+    }
+
+    /// JUnit XML test suite with one test case per language, for CI test result dashboards
+    pub fn export_junit_xml(&self, results: &[(BenchmarkLanguage, bool)]) -> Result<String, Error> {
+        let failures = results.iter().filter(|(_, passed)| !passed).count();
+        let mut xml = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <testsuite name=\"{}\" tests=\"{}\" failures=\"{failures}\">\n",
+            html::escape(&self.name),
+            results.len()
+        );
+        for (lang, passed) in results {
+            let case = format!(
+                "  <testcase name=\"{}\" classname=\"{}\"",
+                html::escape(&lang.to_string()),
+                html::escape(&self.name)
+            );
+            if *passed {
+                xml.push_str(&format!("{case}/>\n"));
+            } else {
+                xml.push_str(&format!(
+                    "{case}>\n    <failure message=\"{} failed\"/>\n  </testcase>\n",
+                    html::escape(&lang.to_string())
+                ));
+            }
+        }
+        xml.push_str("</testsuite>\n");
+        Ok(xml)
+    }
+
+    /// Compiler warnings as a SARIF 2.1.0 log, with one run per compiler
+    /// Files are given relative to the working directory, or as `file://` URIs outside of it
+    pub fn export_sarif_report(&self, issues: &[CompileWarning]) -> Result<String, Error> {
+        let mut langs: Vec<BenchmarkLanguage> = vec![];
+        for issue in issues {
+            if !langs.contains(&issue.lang) {
+                langs.push(issue.lang);
+            }
+        }
+        let cwd = std::env::current_dir().ok();
+        let mut runs = vec![];
+        for lang in langs {
+            let results: Vec<serde_json::Value> = issues
+                .iter()
+                .filter(|issue| issue.lang == lang)
+                .map(|issue| {
+                    let file = issue
+                        .file
+                        .as_ref()
+                        .map_or_else(|| self.source_path(&lang), PathBuf::from);
+                    let uri = sarif_uri(&file, cwd.as_deref());
+                    let mut region = serde_json::Map::new();
+                    if let Some(line) = issue.line {
+                        region.insert("startLine".to_owned(), line.into());
+                    }
+                    if let Some(column) = issue.column {
+                        region.insert("startColumn".to_owned(), column.into());
+                    }
+                    let mut location = serde_json::json!({ "artifactLocation": { "uri": uri } });
+                    if !region.is_empty() {
+                        location["region"] = region.into();
+                    }
+                    serde_json::json!({
+                        "level": "warning",
+                        "message": { "text": issue.message },
+                        "locations": [{ "physicalLocation": location }],
+                    })
+                })
+                .collect();
+            runs.push(serde_json::json!({
+                "tool": { "driver": { "name": lang.compiler() } },
+                "results": results,
+            }));
+        }
+
+        let sarif = serde_json::json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": runs,
+        });
+        serde_json::to_string_pretty(&sarif).map_err(|err| Error::external_tool("sarif", err))
+    }
+
+    /// Html page showing the sources of `lang_a` and `lang_b` side by side
+    pub fn generate_html_diff_view(
+        &self,
+        lang_a: &BenchmarkLanguage,
+        lang_b: &BenchmarkLanguage,
+    ) -> Result<String, Error> {
+        let read_source = |lang: &BenchmarkLanguage| {
+            let source_path = self.source_path(lang);
+            std::fs::read_to_string(&source_path)
+                .map_err(|err| Error::file_access(&source_path, "read source", err))
+        };
+        let (source_a, source_b) = (read_source(lang_a)?, read_source(lang_b)?);
+        let (lines_a, lines_b): (Vec<&str>, Vec<&str>) =
+            (source_a.lines().collect(), source_b.lines().collect());
+
+        let mut rows = String::new();
+        for ind in 0..lines_a.len().max(lines_b.len()) {
+            let cell = |lines: &[&str]| match lines.get(ind) {
+                Some(line) => format!(
+                    "<td class=\"ln\">{}</td><td>{}</td>",
+                    ind + 1,
+                    html::highlight_line(line)
+                ),
+                None => "<td class=\"ln\"></td><td></td>".to_owned(),
+            };
+            rows.push_str(&format!("<tr>{}{}</tr>\n", cell(&lines_a), cell(&lines_b)));
+        }
+        let body = format!(
+            "<h1>{}</h1>\n<table>\n<tr><th colspan=\"2\">{lang_a}</th><th colspan=\"2\">{lang_b}</th></tr>\n{rows}</table>",
+            html::escape(&self.name)
+        );
+        let style = "table { border-collapse: collapse; font-family: monospace; width: 100%; }\n\
+                     td { white-space: pre; vertical-align: top; padding: 0 0.5em; }\n\
+                     td.ln { color: #999; text-align: right; border-left: 1px solid #ccc; }\n\
+                     .kw { color: #8f1e1c; font-weight: bold; }\n.num { color: #1b4253; }\n\
+                     .str { color: #2e7d32; }\n.com { color: #999; font-style: italic; }";
+        Ok(html::page(
+            &format!("{}: {lang_a} vs {lang_b}", self.name),
+            style,
+            &body,
+        ))
+    }
+
+    /// Output path passed to the compiler of `lang`, `<bin>/<arch>/<name>_<suffix>`
+    /// `bin_path` adds the file extension and, for Effekt, the directory the compiler
+    /// places the binary in
+    /// ```
+    /// # use lib::{benchmark::Benchmark, config::Config, langs::BenchmarkLanguage, paths::Paths};
+    /// let bench = Benchmark {
+    ///     name: "Fib".to_owned(),
+    ///     source_name: "Fib".to_owned(),
+    ///     base_path: "suite/Fib".into(),
+    ///     languages: vec![BenchmarkLanguage::Rust],
+    ///     config: Config::default(),
+    ///     paths: Paths { bin: std::env::temp_dir().join("compile_out_path_bin") },
+    /// }
+    /// .with_name_prefix("gc_");
+    /// let rust = BenchmarkLanguage::Rust;
+    /// let cmd = rust.compile_cmd(
+    ///     &bench.source_path(&rust),
+    ///     &bench.compile_out_path(&rust).unwrap(),
+    ///     None,
+    /// );
+    /// let bin_path = bench.bin_path(&rust).unwrap();
+    /// assert!(bin_path.ends_with("gc_Fib_rust"));
+    /// assert!(cmd.get_args().any(|arg| arg == bin_path.as_os_str()));
+    /// ```
+    pub fn compile_out_path(&self, lang: &BenchmarkLanguage) -> Result<PathBuf, Error> {
         #[cfg(target_arch = "x86_64")]
-        let bin_path = bin_path_x86();
+        let bin_dir = self.paths.bin_path_x86();
         #[cfg(target_arch = "aarch64")]
-        let bin_path = bin_path_aarch();
+        let bin_dir = self.paths.bin_path_aarch();
 
-        create_dir_all(&bin_path)
-            .map_err(|_| Error::path_access(&PathBuf::from(&bin_path), "create bin path"))?;
+        create_dir_all(&bin_dir)
+            .map_err(|_| Error::path_access(&PathBuf::from(&bin_dir), "create bin path"))?;
         let mut bin_name = self.name.clone();
-
         if *lang != BenchmarkLanguage::Scc {
             bin_name += "_";
             bin_name += lang.suffix();
         }
-        let bin_path = if *lang == BenchmarkLanguage::Effekt {
-            bin_path.join(bin_name).join(&self.name)
+        Ok(bin_dir.join(bin_name))
+    }
+
+    pub fn bin_path(&self, lang: &BenchmarkLanguage) -> Result<PathBuf, Error> {
+        let out_path = self.compile_out_path(lang)?;
+        let mut bin_path = if *lang == BenchmarkLanguage::Effekt {
+            out_path.join(&self.source_name)
         } else {
-            bin_path.join(bin_name)
+            out_path
         };
+        if let Some(ext) = lang.binary_extension() {
+            bin_path.set_extension(ext);
+        }
 
         Ok(bin_path)
     }
 
+    /// Inspects the compiled binary of `lang` with `file` and `ldd`
+    pub fn export_binary_info(&self, lang: &BenchmarkLanguage) -> Result<BinaryInfo, Error> {
+        let bin_path = self.bin_path(lang)?;
+        let size_bytes = metadata(&bin_path)
+            .map_err(|err| Error::file_access(&bin_path, "read binary metadata", err))?
+            .len();
+
+        let out = Command::new("file")
+            .arg("--brief")
+            .arg(&bin_path)
+            .output()
+            .map_err(|err| Error::external_tool("file", err))?;
+        if !out.status.success() {
+            return Err(Error::external_tool(
+                "file",
+                str::from_utf8(&out.stderr).unwrap_or("exited with nonzero exit status"),
+            ));
+        }
+        let mut info = BinaryInfo::from_file_output(str::from_utf8(&out.stdout).unwrap_or(""))?;
+        info.size_bytes = size_bytes;
+
+        // ldd fails for statically linked binaries, which have no dynamic libraries anyway
+        #[cfg(target_os = "linux")]
+        if let Ok(out) = Command::new("ldd").arg(&bin_path).output() {
+            info.dynamic_libs =
+                BinaryInfo::libs_from_ldd_output(str::from_utf8(&out.stdout).unwrap_or(""));
+        }
+
+        Ok(info)
+    }
+
+    /// Size in bytes of the compiled binary of every compiled language
+    pub fn all_binary_sizes(&self) -> Result<Vec<(BenchmarkLanguage, u64)>, Error> {
+        let mut sizes = vec![];
+        for lang in self.languages_sorted() {
+            if !lang.is_compiled() {
+                continue;
+            }
+            let bin_path = self.bin_path(&lang)?;
+            let size = metadata(&bin_path)
+                .map_err(|err| Error::file_access(&bin_path, "read binary metadata", err))?
+                .len();
+            sizes.push((lang, size));
+        }
+        Ok(sizes)
+    }
+
+    /// Binary sizes of all compiled languages, from smallest to largest
+    pub fn compare_binary_sizes(&self) -> Result<Vec<(BenchmarkLanguage, u64)>, Error> {
+        let mut sizes = self.all_binary_sizes()?;
+        sizes.sort_by_key(|(_, size)| *size);
+        Ok(sizes)
+    }
+
     pub fn result_path(&self) -> Result<PathBuf, Error> {
         create_dir_all(RAW_PATH)
             .map_err(|_| Error::path_access(&PathBuf::from(RAW_PATH), "create hyperfine path"))?;
@@ -89,6 +553,194 @@ impl Benchmark {
         Ok(path)
     }
 
+    /// Result csv of the run started at `timestamp` (seconds since the epoch), used with `archive`
+    pub fn archive_result_path(&self, timestamp: u64) -> Result<PathBuf, Error> {
+        create_dir_all(RAW_PATH)
+            .map_err(|_| Error::path_access(&PathBuf::from(RAW_PATH), "create hyperfine path"))?;
+        let mut path = PathBuf::from(RAW_PATH).join(format!("{}_{timestamp}", self.name));
+        path.set_extension("csv");
+        Ok(path)
+    }
+
+    /// Symlink to the most recent archived result csv
+    pub fn latest_result_path(&self) -> Result<PathBuf, Error> {
+        create_dir_all(RAW_PATH)
+            .map_err(|_| Error::path_access(&PathBuf::from(RAW_PATH), "create hyperfine path"))?;
+        let mut path = PathBuf::from(RAW_PATH).join(format!("{}_latest", self.name));
+        path.set_extension("csv");
+        Ok(path)
+    }
+
+    /// Points `<name>_latest.csv` to `archived`
+    #[cfg(unix)]
+    fn link_latest_result(&self, archived: &Path) -> Result<(), Error> {
+        let latest = self.latest_result_path()?;
+        if latest.symlink_metadata().is_ok() {
+            remove_file(&latest)
+                .map_err(|err| Error::file_access(&latest, "remove latest result link", err))?;
+        }
+        // both files are in the same directory, so the link target is just the file name
+        let target = archived.file_name().map(Path::new).unwrap_or(archived);
+        std::os::unix::fs::symlink(target, &latest)
+            .map_err(|err| Error::file_access(&latest, "link latest result", err))
+    }
+
+    #[cfg(not(unix))]
+    fn link_latest_result(&self, archived: &Path) -> Result<(), Error> {
+        let latest = self.latest_result_path()?;
+        copy(archived, &latest)
+            .map_err(|err| Error::file_access(&latest, "copy latest result", err))?;
+        Ok(())
+    }
+
+    /// Explanation of the columns of hyperfine's csv export, as `column,description,unit`
+    pub fn export_csv_column_descriptions() -> &'static str {
+        "column,description,unit\n\
+         command,command line that was benchmarked,\n\
+         mean,arithmetic mean of the wall clock time of all runs,s\n\
+         stddev,sample standard deviation of the wall clock time,s\n\
+         median,median of the wall clock time,s\n\
+         user,mean time spent in user mode,s\n\
+         system,mean time spent in kernel mode,s\n\
+         min,shortest wall clock time of all runs,s\n\
+         max,longest wall clock time of all runs,s\n"
+    }
+
+    /// Sidecar of the result csv explaining its columns
+    pub fn columns_path(&self) -> Result<PathBuf, Error> {
+        create_dir_all(RAW_PATH)
+            .map_err(|_| Error::path_access(&PathBuf::from(RAW_PATH), "create hyperfine path"))?;
+        let mut path = PathBuf::from(RAW_PATH).join(format!("{}_columns", self.name));
+        path.set_extension("txt");
+        Ok(path)
+    }
+
+    /// Hyperfine's json export, which contains the time of every run
+    pub fn json_result_path(&self) -> Result<PathBuf, Error> {
+        let mut path = self.result_path()?;
+        path.set_extension("json");
+        Ok(path)
+    }
+
+    /// Sidecar listing the languages that were dropped from the last hyperfine run
+    pub fn failed_path(&self) -> Result<PathBuf, Error> {
+        create_dir_all(RAW_PATH)
+            .map_err(|_| Error::path_access(&PathBuf::from(RAW_PATH), "create hyperfine path"))?;
+        let mut path = PathBuf::from(RAW_PATH).join(format!("{}_failed", self.name));
+        path.set_extension("csv");
+        Ok(path)
+    }
+
+    /// All files in the results directory belonging to this benchmark
+    pub fn result_files(&self) -> Result<Vec<PathBuf>, Error> {
+        let raw_path = PathBuf::from(RAW_PATH);
+        if !raw_path.exists() {
+            return Ok(vec![]);
+        }
+        let mut files = vec![];
+        for file in read_dir(&raw_path).map_err(|err| Error::read_dir(&raw_path, err))? {
+            let path = file
+                .map_err(|_| Error::path_access(&raw_path, "Read File"))?
+                .path();
+            let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if file_name.starts_with(&format!("{}.", self.name))
+                || file_name.starts_with(&format!("{}_", self.name))
+            {
+                files.push(path);
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    /// Uploads all (text) result files to a new secret gist and returns its url
+    /// If `token` is empty, `GITHUB_TOKEN` is used instead
+    #[cfg(feature = "async")]
+    pub async fn export_gist(&self, token: &str) -> Result<String, Error> {
+        let token = if token.is_empty() {
+            env::var("GITHUB_TOKEN").map_err(|err| Error::gist(&self.name, err))?
+        } else {
+            token.to_owned()
+        };
+
+        let mut files = serde_json::Map::new();
+        for path in self.result_files()? {
+            // compressed results cannot be uploaded as gist content
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            let file_name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .ok_or(Error::path_access(&path, "File name (as string)"))?;
+            files.insert(
+                file_name.to_owned(),
+                serde_json::json!({ "content": content }),
+            );
+        }
+        if files.is_empty() {
+            return Err(Error::gist(&self.name, "No results to upload"));
+        }
+
+        let body = serde_json::json!({
+            "description": format!("Benchmark results for {}", self.name),
+            "public": false,
+            "files": files,
+        });
+        let response = reqwest::Client::new()
+            .post("https://api.github.com/gists")
+            .bearer_auth(token)
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "sc-bench")
+            .json(&body)
+            .send()
+            .await
+            .map_err(|err| Error::gist(&self.name, err))?
+            .error_for_status()
+            .map_err(|err| Error::gist(&self.name, err))?;
+        let gist: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|err| Error::gist(&self.name, err))?;
+        gist["html_url"]
+            .as_str()
+            .map(|url| url.to_owned())
+            .ok_or(Error::gist(
+                &self.name,
+                "Response does not contain a gist url",
+            ))
+    }
+
+    pub fn metadata_path(&self) -> Result<PathBuf, Error> {
+        create_dir_all(RAW_PATH)
+            .map_err(|_| Error::path_access(&PathBuf::from(RAW_PATH), "create hyperfine path"))?;
+        let mut path = PathBuf::from(RAW_PATH).join(format!("{}_meta", self.name));
+        path.set_extension("json");
+        Ok(path)
+    }
+
+    /// Model, core count and base frequency of the CPU, from `/proc/cpuinfo` on Linux,
+    /// `sysctl` on macOS and `wmic` on Windows
+    pub fn detect_processor_info() -> ProcessorInfo {
+        ProcessorInfo::detect()
+    }
+
+    /// Times `true` `runs` times with hyperfine to calibrate the machine
+    /// Timings are considered acceptable if their coefficient of variation is below 5%
+    pub fn check_for_timing_noise(runs: u32) -> Result<TimingNoiseResult, Error> {
+        let times = run_hyperfine_single("true", runs)?;
+        Ok(TimingNoiseResult::from_times(&times))
+    }
+
+    pub fn write_metadata_json(&self, metadata: &Metadata) -> Result<(), Error> {
+        let path = self.metadata_path()?;
+        let contents = serde_json::to_string_pretty(metadata)
+            .map_err(|err| Error::file_access(&path, "serialize metadata", err))?;
+        write(&path, contents).map_err(|err| Error::file_access(&path, "write metadata", err))
+    }
+
     pub fn results_exist(&self) -> Result<bool, Error> {
         let out_path = self.result_path()?;
         if !out_path.exists() {
@@ -97,27 +749,96 @@ impl Benchmark {
         Ok(true)
     }
 
-    pub fn compile_all(&self) -> Result<(), Error> {
+    /// Replaces the result csv with a gzip-compressed `<name>.csv.gz`
+    #[cfg(feature = "compression")]
+    pub fn compress_results(&self) -> Result<(), Error> {
+        use flate2::{Compression, write::GzEncoder};
+
+        let out_path = self.result_path()?;
+        let mut gz_path = out_path.clone();
+        gz_path.set_extension("csv.gz");
+
+        let mut input = std::fs::File::open(&out_path)
+            .map_err(|err| Error::file_access(&out_path, "open results", err))?;
+        let output = std::fs::File::create(&gz_path)
+            .map_err(|err| Error::file_access(&gz_path, "create compressed results", err))?;
+        let mut encoder = GzEncoder::new(output, Compression::default());
+        std::io::copy(&mut input, &mut encoder)
+            .map_err(|err| Error::file_access(&gz_path, "compress results", err))?;
+        encoder
+            .finish()
+            .map_err(|err| Error::file_access(&gz_path, "compress results", err))?;
+
+        remove_file(&out_path).map_err(|err| Error::file_access(&out_path, "remove results", err))
+    }
+
+    /// Restores the result csv from `<name>.csv.gz`, inverse of `compress_results`
+    #[cfg(feature = "compression")]
+    pub fn decompress_results(&self) -> Result<(), Error> {
+        use flate2::read::GzDecoder;
+
+        let out_path = self.result_path()?;
+        let mut gz_path = out_path.clone();
+        gz_path.set_extension("csv.gz");
+
+        let input = std::fs::File::open(&gz_path)
+            .map_err(|err| Error::file_access(&gz_path, "open compressed results", err))?;
+        let mut output = std::fs::File::create(&out_path)
+            .map_err(|err| Error::file_access(&out_path, "create results", err))?;
+        std::io::copy(&mut GzDecoder::new(input), &mut output)
+            .map_err(|err| Error::file_access(&out_path, "decompress results", err))?;
+
+        remove_file(&gz_path)
+            .map_err(|err| Error::file_access(&gz_path, "remove compressed results", err))
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(benchmark = %self.name))
+    )]
+    pub fn compile_all(&self) -> Result<Vec<CompileOutput>, Error> {
+        let mut outputs = vec![];
         for lang in self.languages.iter() {
-            self.compile(lang)?;
+            outputs.push(self.compile(lang)?);
         }
-        Ok(())
+        Ok(outputs)
     }
 
-    pub fn compile(&self, lang: &BenchmarkLanguage) -> Result<(), Error> {
+    /// Compiles `lang`, returning the warnings the compiler printed
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(benchmark = %self.name, lang = %lang))
+    )]
+    pub fn compile(&self, lang: &BenchmarkLanguage) -> Result<CompileOutput, Error> {
         if !self.languages.contains(lang) {
             return Err(Error::unknown_lang("Compiling", lang));
         }
 
+        let start = Instant::now();
+        if !lang.is_compiled() {
+            return Ok(CompileOutput::from_output(*lang, &[], &[], Duration::ZERO));
+        }
+
         // Special pipeline for MoonBit: use moonc to build/link core to C, then cc to build executable
         if let BenchmarkLanguage::MoonBit = lang {
-            return self.compile_moonbit();
+            return self.compile_moonbit(start);
+        }
+        if let BenchmarkLanguage::Gleam = lang {
+            return self.compile_gleam(start);
+        }
+        // compilers that are rarely installed get a clear error instead of a failed spawn
+        if matches!(
+            lang,
+            BenchmarkLanguage::Verse | BenchmarkLanguage::Ante | BenchmarkLanguage::Carbon
+        ) && let Some(tool) = lang.required_tools().iter().find(|tool| !on_path(tool))
+        {
+            return Err(Error::missing_tool(tool));
         }
 
-        let mut source_path = self.base_path.clone().join(&self.name);
-        source_path.set_extension(lang.ext());
-
-        let mut compile_cmd = lang.compile_cmd(&source_path, self.config.heap_size);
+        let source_path = self.source_path(lang);
+        let out_path = self.compile_out_path(lang)?;
+        let mut compile_cmd = lang.compile_cmd(&source_path, &out_path, self.config.heap_size);
+        self.set_compile_env(&mut compile_cmd);
 
         let out = compile_cmd
             .output()
@@ -128,6 +849,14 @@ impl Benchmark {
             str::from_utf8(&out.stdout).unwrap_or(""),
             str::from_utf8(&out.stderr).unwrap_or(""),
         ))?;
+        // scc names its output after the source file, so prefixed benchmarks move it
+        if let BenchmarkLanguage::Scc = lang
+            && self.name != self.source_name
+        {
+            let built = out_path.with_file_name(&self.source_name);
+            rename(&built, &out_path)
+                .map_err(|err| Error::file_access(&built, "Rename scc binary", err))?;
+        }
         // for Koka, we have to make the generated binary executable
         if let BenchmarkLanguage::Koka = lang {
             let bin_path = self.bin_path(lang)?;
@@ -142,11 +871,70 @@ impl Benchmark {
                 .then_some(())
                 .ok_or(Error::path_access(&bin_path, "Change file permissions"))?
         };
+        Ok(CompileOutput::from_output(
+            *lang,
+            &out.stdout,
+            &out.stderr,
+            start.elapsed(),
+        ))
+    }
+
+    /// Compiler used by `compile` for each language of this benchmark that can be built
+    /// with several compilers, picked from the installed ones by priority
+    /// Only OCaml has a choice, between compilers differing in compile time only
+    pub fn auto_select_best_compiler(&self) -> HashMap<BenchmarkLanguage, String> {
+        self.languages
+            .iter()
+            .filter_map(|lang| Some((*lang, lang.best_compiler()?.to_owned())))
+            .collect()
+    }
+
+    /// Compiles `lang` and fails if compilation took longer than `limit`
+    pub fn assert_compile_time_under(
+        &self,
+        lang: &BenchmarkLanguage,
+        limit: Duration,
+    ) -> Result<(), Error> {
+        let actual = self.compile(lang)?.duration;
+        if actual > limit {
+            return Err(Error::compile_time_exceeded(
+                &self.name, lang, limit, actual,
+            ));
+        }
         Ok(())
     }
 
-    fn compile_moonbit(&self) -> Result<(), Error> {
-        let mut source_path = self.base_path.clone().join(&self.name);
+    /// With `env_passthrough`, clears the environment of `cmd` except for the listed
+    /// variables and the ones most tools need to work at all
+    fn apply_env_passthrough(&self, cmd: &mut Command) {
+        if self.config.env_passthrough.is_empty() {
+            return;
+        }
+        cmd.env_clear();
+        let essentials = ["PATH", "HOME", "USER", "LANG", "TMPDIR", "TERM"];
+        for var in essentials
+            .iter()
+            .copied()
+            .chain(self.config.env_passthrough.iter().map(|var| var.as_str()))
+        {
+            if let Some(value) = env::var_os(var) {
+                cmd.env(var, value);
+            }
+        }
+    }
+
+    fn set_compile_env(&self, cmd: &mut Command) {
+        self.apply_env_passthrough(cmd);
+        if !self.config.c_flags.is_empty() {
+            cmd.env("CFLAGS", self.config.c_flags.join(" "));
+        }
+        if !self.config.cxx_flags.is_empty() {
+            cmd.env("CXXFLAGS", self.config.cxx_flags.join(" "));
+        }
+    }
+
+    fn compile_moonbit(&self, start: Instant) -> Result<CompileOutput, Error> {
+        let mut source_path = self.base_path.clone().join(&self.source_name);
         source_path.set_extension(BenchmarkLanguage::MoonBit.ext());
         let workspace = PathBuf::from("target_scc").join("moon_workspace");
         // create_dir_all(&workspace)
@@ -158,11 +946,18 @@ impl Benchmark {
 
         let mut build_cmd = Command::new("moon");
         build_cmd.arg("build");
-        build_cmd.args(["--target", "native", "--release"]);
+        build_cmd.args(["--target", "native"]);
+        build_cmd.args(self.config.build_mode.to_flag());
         build_cmd.current_dir(&workspace);
-        let out = build_cmd
-            .output()
-            .map_err(|err| Error::compile(&self.name, &BenchmarkLanguage::MoonBit, "", &err.to_string()))?;
+        self.set_compile_env(&mut build_cmd);
+        let out = build_cmd.output().map_err(|err| {
+            Error::compile(
+                &self.name,
+                &BenchmarkLanguage::MoonBit,
+                "",
+                &err.to_string(),
+            )
+        })?;
         if !out.status.success() {
             return Err(Error::compile(
                 &self.name,
@@ -176,47 +971,232 @@ impl Benchmark {
         let built = workspace
             .join("target")
             .join("native")
-            .join("release")
+            .join(self.config.build_mode.target_dir())
             .join("build")
             .join("benchmoon.exe");
 
         rename(&built, &out_path)
             .map_err(|e| Error::file_access(&out_path, "move MoonBit binary", e))?;
 
-        Ok(())
+        Ok(CompileOutput::from_output(
+            BenchmarkLanguage::MoonBit,
+            &out.stdout,
+            &out.stderr,
+            start.elapsed(),
+        ))
     }
 
-    pub fn run_all(&self, test: bool) -> Result<Vec<std::process::Output>, Error> {
-        let mut results = vec![];
-        for lang in self.languages.iter() {
-            let res = self.run(lang, test)?;
-            results.push(res);
+    /// Stages the source as `src/benchgleam.gleam` of a generated Gleam project,
+    /// builds it for the native target and moves the binary to `bin_path`
+    fn compile_gleam(&self, start: Instant) -> Result<CompileOutput, Error> {
+        let lang = BenchmarkLanguage::Gleam;
+        let mut source_path = self.base_path.clone().join(&self.source_name);
+        source_path.set_extension(lang.ext());
+        let workspace = PathBuf::from("target_scc").join("gleam_workspace");
+        let src_dir = workspace.join("src");
+        create_dir_all(&src_dir)
+            .map_err(|e| Error::file_access(&src_dir, "create gleam project", e))?;
+
+        let manifest = workspace.join("gleam.toml");
+        write(&manifest, "name = \"benchgleam\"\nversion = \"1.0.0\"\n")
+            .map_err(|e| Error::file_access(&manifest, "write gleam.toml", e))?;
+        let dst_file = src_dir.join("benchgleam.gleam");
+        copy(&source_path, &dst_file)
+            .map_err(|e| Error::file_access(&dst_file, "copy gleam file", e))?;
+
+        let mut build_cmd = Command::new("gleam");
+        build_cmd.arg("build");
+        build_cmd.args(["--target", "native"]);
+        build_cmd.current_dir(&workspace);
+        self.set_compile_env(&mut build_cmd);
+        let out = build_cmd
+            .output()
+            .map_err(|err| Error::compile(&self.name, &lang, "", &err.to_string()))?;
+        if !out.status.success() {
+            return Err(Error::compile(
+                &self.name,
+                &lang,
+                str::from_utf8(&out.stdout).unwrap_or(""),
+                str::from_utf8(&out.stderr).unwrap_or(""),
+            ));
         }
-        Ok(results)
+
+        let out_path = self.bin_path(&lang)?;
+        let built = workspace.join("build").join("native").join("benchgleam");
+        rename(&built, &out_path)
+            .map_err(|e| Error::file_access(&out_path, "move Gleam binary", e))?;
+
+        Ok(CompileOutput::from_output(
+            lang,
+            &out.stdout,
+            &out.stderr,
+            start.elapsed(),
+        ))
+    }
+
+    /// Runs every language, collecting the outcomes of failing languages instead of aborting
+    /// With `config.strict`, the first failure is returned as an error instead
+    /// Outcomes are in the order of `languages_sorted`, independent of how the benchmark was loaded
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(benchmark = %self.name))
+    )]
+    pub fn run_all(&self, test: bool) -> Result<Vec<RunOutcome>, Error> {
+        let mut outcomes = vec![];
+        for lang in self.languages_sorted().iter() {
+            if test || self.config.scenarios.is_empty() {
+                let args = if test {
+                    &self.config.test_args
+                } else {
+                    &self.config.args
+                };
+                outcomes.push(self.run_outcome(lang, None, args)?);
+            } else {
+                for (label, args) in self.config.scenarios.iter() {
+                    outcomes.push(self.run_outcome(lang, Some(label), args)?);
+                }
+            }
+            if self.config.strict {
+                self.first_failure(&outcomes)?;
+            }
+        }
+        Ok(outcomes)
+    }
+
+    pub fn first_failure(&self, outcomes: &[RunOutcome]) -> Result<(), Error> {
+        match outcomes.iter().find(|outcome| !outcome.success()) {
+            None => Ok(()),
+            Some(outcome) if outcome.status.is_none() => {
+                Err(Error::run(&self.name, &outcome.lang, outcome.stderr_str()))
+            }
+            Some(outcome) => Err(Error::run(
+                &self.name,
+                &outcome.lang,
+                "Command exited with nonzero exit status",
+            )),
+        }
+    }
+
+    fn run_outcome(
+        &self,
+        lang: &BenchmarkLanguage,
+        scenario: Option<&str>,
+        args: &[String],
+    ) -> Result<RunOutcome, Error> {
+        let mut cmd = self.run_cmd(lang)?;
+        cmd.args(args);
+        self.set_stdin(&mut cmd)?;
+        let start = Instant::now();
+        let (status, stdout, stderr) = match cmd.output() {
+            Ok(out) => (Some(out.status), out.stdout, out.stderr),
+            Err(err) => (None, vec![], err.to_string().into_bytes()),
+        };
+        Ok(RunOutcome {
+            lang: *lang,
+            scenario: scenario.map(|label| label.to_owned()),
+            status,
+            stdout,
+            stderr,
+            duration: start.elapsed(),
+        })
     }
 
     pub fn run_cmd(&self, lang: &BenchmarkLanguage) -> Result<Command, Error> {
+        let mut cmd = self.base_run_cmd(lang)?;
+        self.apply_env_passthrough(&mut cmd);
+        Ok(cmd)
+    }
+
+    fn base_run_cmd(&self, lang: &BenchmarkLanguage) -> Result<Command, Error> {
+        if *lang == BenchmarkLanguage::Fennel {
+            let mut cmd = Command::new("fennel");
+            cmd.arg(self.source_path(lang));
+            return Ok(cmd);
+        }
+        // the lobster command compiles to bytecode and runs it in one go
+        if *lang == BenchmarkLanguage::Lobster {
+            let mut cmd = Command::new("lobster");
+            cmd.arg(self.source_path(lang));
+            return Ok(cmd);
+        }
+        // ucm keeps code in a content-addressed codebase, so the source has to be
+        // a scratch file defining a `main` function, which `ucm run` adds and runs
+        if *lang == BenchmarkLanguage::Unison {
+            let mut cmd = Command::new("ucm");
+            cmd.arg("run");
+            cmd.arg(self.source_path(lang));
+            return Ok(cmd);
+        }
+
         let bin_path = self.bin_path(lang)?;
-        if *lang == BenchmarkLanguage::SmlNj {
-            let mut cmd = Command::new("sml");
-            cmd.arg("@SMLload");
-            cmd.arg(bin_path);
-            Ok(cmd)
-        } else {
-            Ok(Command::new(bin_path))
+        match lang {
+            BenchmarkLanguage::SmlNj => {
+                let mut cmd = Command::new("sml");
+                cmd.arg("@SMLload");
+                cmd.arg(bin_path);
+                Ok(cmd)
+            }
+            BenchmarkLanguage::Grain => {
+                // arguments appended after this are passed to the wasm module
+                let mut cmd = Command::new("wasmtime");
+                cmd.arg(bin_path);
+                cmd.arg("--");
+                Ok(cmd)
+            }
+            _ => Ok(Command::new(bin_path)),
         }
     }
 
+    /// Appends the command running `lang` with `args` to `wrapper`, e.g. `valgrind` or `taskset`
+    fn wrap_cmd(
+        &self,
+        mut wrapper: Command,
+        lang: &BenchmarkLanguage,
+        args: &[String],
+    ) -> Result<Command, Error> {
+        let run_cmd = self.run_cmd(lang)?;
+        self.apply_env_passthrough(&mut wrapper);
+        wrapper.arg(run_cmd.get_program());
+        wrapper.args(run_cmd.get_args());
+        wrapper.args(args);
+        Ok(wrapper)
+    }
+
     pub fn run(&self, lang: &BenchmarkLanguage, test: bool) -> Result<std::process::Output, Error> {
-        let mut cmd = self.run_cmd(lang)?;
         let args = if test {
             &self.config.test_args
         } else {
             &self.config.args
         };
-        for arg in args {
-            cmd.arg(arg);
+        self.run_with_args(lang, args)
+    }
+
+    /// Compiles (if needed) and runs only `langs`, with the test arguments if `test` is set
+    /// Fails before compiling anything if one of `langs` is not part of this benchmark
+    pub fn run_language_subset(
+        &self,
+        langs: &[BenchmarkLanguage],
+        test: bool,
+    ) -> Result<HashMap<BenchmarkLanguage, std::process::Output>, Error> {
+        if let Some(lang) = langs.iter().find(|lang| !self.languages.contains(lang)) {
+            return Err(Error::unknown_lang("Running", lang));
+        }
+        let mut outputs = HashMap::with_capacity(langs.len());
+        for lang in langs {
+            self.compile(lang)?;
+            outputs.insert(*lang, self.run(lang, test)?);
         }
+        Ok(outputs)
+    }
+
+    pub fn run_with_args(
+        &self,
+        lang: &BenchmarkLanguage,
+        args: &[String],
+    ) -> Result<std::process::Output, Error> {
+        let mut cmd = self.run_cmd(lang)?;
+        cmd.args(args);
+        self.set_stdin(&mut cmd)?;
         let out = cmd
             .output()
             .map_err(|err| Error::run(&self.name, lang, err))?;
@@ -230,65 +1210,1973 @@ impl Benchmark {
         Ok(out)
     }
 
-    pub fn run_hyperfine_all(&self) -> Result<(), Error> {
+    /// Input generated by `generate_input_file`
+    pub fn input_path(&self) -> Result<PathBuf, Error> {
+        create_dir_all(RAW_PATH)
+            .map_err(|_| Error::path_access(&PathBuf::from(RAW_PATH), "create hyperfine path"))?;
+        let mut path = PathBuf::from(RAW_PATH).join(format!("{}_input", self.name));
+        path.set_extension("bin");
+        Ok(path)
+    }
+
+    /// Runs the generator `<name>_gen` from the benchmark directory with `--size <size>`
+    /// and saves its output to `input_path`, which is used as stdin for later runs
+    /// unless the config sets a `stdin_file`
+    pub fn generate_input_file(&self, size: u64) -> Result<PathBuf, Error> {
+        let generator = self.base_path.join(format!("{}_gen", self.source_name));
+        if !generator.is_file() {
+            return Err(Error::path_access(&generator, "input generator"));
+        }
+        let path = self.input_path()?;
+        let output =
+            File::create(&path).map_err(|err| Error::file_access(&path, "create input", err))?;
+        let status = Command::new(&generator)
+            .args(["--size", &size.to_string()])
+            .stdout(output)
+            .status()
+            .map_err(|err| Error::file_access(&generator, "run input generator", err))?;
+        if !status.success() {
+            return Err(Error::external_tool(
+                &format!("{}_gen", self.source_name),
+                status,
+            ));
+        }
+        Ok(path)
+    }
+
+    /// Whether the generated input exists and is newer than the config file
+    /// If not, it needs to be regenerated with `generate_input_file`
+    pub fn verify_input_file_freshness(&self) -> Result<bool, Error> {
+        let input_path = self.input_path()?;
+        if !input_path.exists() {
+            return Ok(false);
+        }
+        let modified = |path: &Path| {
+            metadata(path)
+                .and_then(|meta| meta.modified())
+                .map_err(|err| Error::file_access(path, "read modification time", err))
+        };
+        let config_path = self.config_path();
+        if !config_path.exists() {
+            return Ok(true);
+        }
+        Ok(modified(&input_path)? > modified(&config_path)?)
+    }
+
+    /// File used as stdin: the configured `stdin_file`, or else the generated input
+    pub fn stdin_file(&self) -> Result<Option<PathBuf>, Error> {
+        if let Some(stdin_file) = &self.config.stdin_file {
+            return Ok(Some(stdin_file.clone()));
+        }
+        let input_path = self.input_path()?;
+        Ok(input_path.exists().then_some(input_path))
+    }
+
+    fn set_stdin(&self, cmd: &mut Command) -> Result<(), Error> {
+        if let Some(path) = self.stdin_file()? {
+            let input =
+                File::open(&path).map_err(|err| Error::file_access(&path, "open input", err))?;
+            cmd.stdin(input);
+        }
+        Ok(())
+    }
+
+    /// Runs `lang` with `args`, reading stdin from the pre-generated `input_path`
+    pub fn run_with_input_file(
+        &self,
+        lang: &BenchmarkLanguage,
+        input_path: &Path,
+    ) -> Result<std::process::Output, Error> {
+        let input = File::open(input_path)
+            .map_err(|err| Error::file_access(input_path, "open input", err))?;
+        let mut cmd = self.run_cmd(lang)?;
+        cmd.args(&self.config.args);
+        cmd.stdin(input);
+        let out = cmd
+            .output()
+            .map_err(|err| Error::run(&self.name, lang, err))?;
+        if !out.status.success() {
+            return Err(Error::run(
+                &self.name,
+                lang,
+                "Command exited with nonzero exit status",
+            ));
+        }
+        Ok(out)
+    }
+
+    /// Shell command string for running `lang` with `args`, as passed to hyperfine
+    pub fn call_str(&self, lang: &BenchmarkLanguage, args: &[String]) -> Result<String, Error> {
+        let cmd = self.run_cmd(lang)?;
+        let bin_path = self.bin_path(lang)?;
+        let path_err = || Error::path_access(&bin_path, "Path as String");
+
+        // hyperfine runs commands through a shell, so arguments with spaces need quoting
+        let mut call_str = shell_quote(cmd.get_program().to_str().ok_or_else(path_err)?);
+        for arg in cmd.get_args() {
+            call_str.push(' ');
+            call_str.push_str(&shell_quote(arg.to_str().ok_or_else(path_err)?));
+        }
+        for arg in args {
+            call_str.push(' ');
+            call_str.push_str(&shell_quote(arg));
+        }
+        Ok(call_str)
+    }
+
+    /// Runs `lang` on the test inputs `runs` times and checks that stdout is always the same
+    pub fn check_output_determinism(
+        &self,
+        lang: &BenchmarkLanguage,
+        runs: u32,
+    ) -> Result<bool, Error> {
+        let first = self.run(lang, true)?.stdout;
+        for _ in 1..runs {
+            if self.run(lang, true)?.stdout != first {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Runs `lang` on the test inputs and checks the number of lines it printed
+    pub fn assert_output_line_count(
+        &self,
+        lang: &BenchmarkLanguage,
+        expected_lines: usize,
+    ) -> Result<(), Error> {
+        let out = self.run(lang, true)?;
+        let got = str::from_utf8(&out.stdout).unwrap_or("").lines().count();
+        if got != expected_lines {
+            return Err(Error::output_line_mismatch(
+                &self.name,
+                lang,
+                expected_lines,
+                got,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Runs `lang` with its address space limited to `max_rss_bytes` (`RLIMIT_AS`)
+    /// Unlike `run_under_cgroup`, this needs no privileges, but also counts virtual memory
+    /// that is reserved and never used, which some runtimes do a lot of
+    /// Failed runs are only reported as `Error::OomKilled` if stderr mentions a failed
+    /// allocation, other crashes are reported as `Error::Run` with the signal
+    #[cfg(unix)]
+    pub fn run_with_resource_limits(
+        &self,
+        lang: &BenchmarkLanguage,
+        max_rss_bytes: u64,
+        test: bool,
+    ) -> Result<std::process::Output, Error> {
+        use nix::sys::{
+            resource::{Resource, setrlimit},
+            signal::Signal,
+        };
+        use std::os::unix::process::{CommandExt, ExitStatusExt};
+
+        let args = if test {
+            &self.config.test_args
+        } else {
+            &self.config.args
+        };
+        let mut cmd = self.run_cmd(lang)?;
+        cmd.args(args);
+        self.set_stdin(&mut cmd)?;
+        // SAFETY: setrlimit is async-signal-safe and does not allocate
+        unsafe {
+            cmd.pre_exec(move || {
+                setrlimit(Resource::RLIMIT_AS, max_rss_bytes, max_rss_bytes)
+                    .map_err(std::io::Error::from)
+            });
+        }
+        let out = cmd
+            .output()
+            .map_err(|err| Error::run(&self.name, lang, err.to_string()))?;
+
+        if out.status.success() {
+            return Ok(out);
+        }
+        // a crash alone is no evidence of a failed allocation, the runtime has to report it
+        let stderr = String::from_utf8_lossy(&out.stderr).to_lowercase();
+        if OOM_MARKERS.iter().any(|marker| stderr.contains(marker)) {
+            return Err(Error::oom_killed(
+                &self.name,
+                lang,
+                Some(max_rss_bytes / (1024 * 1024)),
+            ));
+        }
+        match out.status.signal().map(|signal| {
+            Signal::try_from(signal).map_or(signal.to_string(), |sig| sig.to_string())
+        }) {
+            Some(signal) => Err(Error::run(
+                &self.name,
+                lang,
+                format!("Command was killed by {signal}"),
+            )),
+            None => Err(Error::run(
+                &self.name,
+                lang,
+                "Command exited with nonzero exit status",
+            )),
+        }
+    }
+
+    /// Runs `lang` in new network, IPC and PID namespaces, so it has no network access
+    /// and cannot see other processes. Requires `unshare` and usually root
+    #[cfg(target_os = "linux")]
+    pub fn sandbox_run(
+        &self,
+        lang: &BenchmarkLanguage,
+        test: bool,
+    ) -> Result<std::process::Output, Error> {
+        let args = if test {
+            &self.config.test_args
+        } else {
+            &self.config.args
+        };
+        let mut unshare = Command::new("unshare");
+        unshare.args(["--net", "--ipc", "--pid", "--fork", "--mount-proc"]);
+        let mut cmd = self.wrap_cmd(unshare, lang, args)?;
+        self.set_stdin(&mut cmd)?;
+        cmd.output()
+            .map_err(|err| Error::external_tool("unshare", err))
+    }
+
+    /// Counts the syscalls of a run of `lang` with `strace -c`, `strace_args` are passed
+    /// to strace before the command, e.g. `["-f"]` to follow child processes
+    #[cfg(target_os = "linux")]
+    pub fn run_with_strace(
+        &self,
+        lang: &BenchmarkLanguage,
+        strace_args: &[&str],
+    ) -> Result<StraceResult, Error> {
+        if !on_path("strace") {
+            return Err(Error::missing_tool("strace"));
+        }
+        let mut strace = Command::new("strace");
+        strace.arg("-c");
+        strace.args(strace_args);
+        strace.arg("--");
+        let mut cmd = self.wrap_cmd(strace, lang, &self.config.args)?;
+        self.set_stdin(&mut cmd)?;
+        let out = cmd
+            .output()
+            .map_err(|err| Error::external_tool("strace", err))?;
+        if !out.status.success() {
+            return Err(Error::run(
+                &self.name,
+                lang,
+                str::from_utf8(&out.stderr).unwrap_or("Command exited with nonzero exit status"),
+            ));
+        }
+        Ok(StraceResult::from_strace_summary(
+            &self.name,
+            *lang,
+            str::from_utf8(&out.stderr).unwrap_or(""),
+        ))
+    }
+
+    /// Runs `lang` with `args` in a transient cgroup limited to `memory_limit_mb`
+    /// Requires the cgroup tools (`cgcreate`, `cgset`, `cgexec`, `cgdelete`)
+    #[cfg(target_os = "linux")]
+    pub fn run_under_cgroup(
+        &self,
+        lang: &BenchmarkLanguage,
+        memory_limit_mb: u64,
+    ) -> Result<std::process::Output, Error> {
+        let mut cmd = self.run_cmd(lang)?;
+        cmd.args(&self.config.args);
+        self.run_in_cgroup(lang, memory_limit_mb, cmd)
+    }
+
+    /// Runs `lang` pinned to `cpu_core` in a cgroup limited to `memory_limit_mb`
+    /// Frequency scaling is disabled for the duration of the run by setting every
+    /// cpufreq policy to the `performance` governor, which requires root
+    #[cfg(target_os = "linux")]
+    pub fn run_isolated(
+        &self,
+        lang: &BenchmarkLanguage,
+        cpu_core: u32,
+        memory_limit_mb: u64,
+        test: bool,
+    ) -> Result<std::process::Output, Error> {
+        if !on_path("taskset") {
+            return Err(Error::missing_tool("taskset"));
+        }
+        let args = if test {
+            &self.config.test_args
+        } else {
+            &self.config.args
+        };
+        let mut taskset = Command::new("taskset");
+        taskset.args(["-c", &cpu_core.to_string()]);
+        let cmd = self.wrap_cmd(taskset, lang, args)?;
+
+        let governors = set_scaling_governors("performance")?;
+        let out = self.run_in_cgroup(lang, memory_limit_mb, cmd);
+        // a failed restore must not hide the result of the run
+        if let Err(err) = restore_scaling_governors(governors) {
+            log::warn!("{err}");
+        }
+        out
+    }
+
+    #[cfg(target_os = "linux")]
+    fn run_in_cgroup(
+        &self,
+        lang: &BenchmarkLanguage,
+        memory_limit_mb: u64,
+        cmd: Command,
+    ) -> Result<std::process::Output, Error> {
+        if let Some(tool) = ["cgcreate", "cgset", "cgexec", "cgdelete"]
+            .into_iter()
+            .find(|tool| !on_path(tool))
+        {
+            return Err(Error::missing_tool(tool));
+        }
+
+        let group = format!("memory:sc_bench_{}_{}", self.name, lang.suffix());
+        let cgroup_tool = |tool: &str, args: &[&str]| -> Result<(), Error> {
+            let status = Command::new(tool)
+                .args(args)
+                .status()
+                .map_err(|err| Error::external_tool(tool, err))?;
+            if !status.success() {
+                return Err(Error::external_tool(tool, status));
+            }
+            Ok(())
+        };
+
+        cgroup_tool("cgcreate", &["-g", &group])?;
+        // cgroup v2 names the limit memory.max, v1 memory.limit_in_bytes
+        let limit_name = if Path::new("/sys/fs/cgroup/cgroup.controllers").exists() {
+            "memory.max"
+        } else {
+            "memory.limit_in_bytes"
+        };
+        let limit = format!("{limit_name}={}", memory_limit_mb * 1024 * 1024);
+        let out = cgroup_tool("cgset", &["-r", &limit, &group]).and_then(|_| {
+            let mut cgexec = Command::new("cgexec");
+            cgexec.args(["-g", &group]);
+            cgexec.arg(cmd.get_program());
+            cgexec.args(cmd.get_args());
+            for (key, value) in cmd.get_envs() {
+                match value {
+                    Some(value) => cgexec.env(key, value),
+                    None => cgexec.env_remove(key),
+                };
+            }
+            if let Some(dir) = cmd.get_current_dir() {
+                cgexec.current_dir(dir);
+            }
+            self.set_stdin(&mut cgexec)?;
+            cgexec
+                .output()
+                .map_err(|err| Error::run(&self.name, lang, err))
+        });
+        if let Err(err) = cgroup_tool("cgdelete", &["-g", &group]) {
+            log::warn!("{err}");
+        }
+
+        let out = out?;
+        if !out.status.success() {
+            return Err(Error::run(
+                &self.name,
+                lang,
+                "Command exited with nonzero exit status",
+            ));
+        }
+        Ok(out)
+    }
+
+    /// Like `run`, but reports `Error::OomKilled` if the kernel OOM killer ended the process
+    /// Checks the kernel log, falling back to the `oom_kill` counter of the current cgroup
+    #[cfg(target_os = "linux")]
+    pub fn detect_memory_limit_exceeded(
+        &self,
+        lang: &BenchmarkLanguage,
+        test: bool,
+    ) -> Result<std::process::Output, Error> {
+        use std::os::unix::process::ExitStatusExt;
+
+        let args = if test {
+            &self.config.test_args
+        } else {
+            &self.config.args
+        };
+        let oom_kills_before = cgroup_oom_kills();
+        let mut cmd = self.run_cmd(lang)?;
+        cmd.args(args);
+        cmd.stdout(std::process::Stdio::piped());
+        cmd.stderr(std::process::Stdio::piped());
+        let child = cmd
+            .spawn()
+            .map_err(|err| Error::run(&self.name, lang, err))?;
+        let pid = child.id();
+        let out = child
+            .wait_with_output()
+            .map_err(|err| Error::run(&self.name, lang, err))?;
+        if out.status.success() {
+            return Ok(out);
+        }
+
+        // the OOM killer always sends SIGKILL
+        if out.status.signal() == Some(9) {
+            let dmesg = Command::new("dmesg").output();
+            if let Ok(dmesg) = dmesg
+                && dmesg.status.success()
+            {
+                let dmesg = str::from_utf8(&dmesg.stdout).unwrap_or("");
+                if let Some(line) = oom_kill_line(dmesg, pid) {
+                    return Err(Error::oom_killed(&self.name, lang, anon_rss_mb(line)));
+                }
+            } else if oom_kills_before.is_some() && cgroup_oom_kills() > oom_kills_before {
+                return Err(Error::oom_killed(&self.name, lang, None));
+            }
+        }
+        Err(Error::run(
+            &self.name,
+            lang,
+            "Command exited with nonzero exit status",
+        ))
+    }
+
+    /// Runs `lang` on the test inputs `n` times and compares the hashes of the outputs
+    /// Returns `Ok(true)` if every run produced the same stdout
+    pub fn detect_non_deterministic_output(
+        &self,
+        lang: &BenchmarkLanguage,
+        n: u32,
+    ) -> Result<bool, Error> {
+        self.detect_non_deterministic_output_with_threshold(lang, n, 0.0)
+    }
+
+    /// Like `detect_non_deterministic_output`, but up to a `threshold` fraction of the runs
+    /// may differ from the most common output
+    pub fn detect_non_deterministic_output_with_threshold(
+        &self,
+        lang: &BenchmarkLanguage,
+        n: u32,
+        threshold: f64,
+    ) -> Result<bool, Error> {
+        let mut counts: HashMap<u64, u32> = HashMap::new();
+        for _ in 0..n {
+            let mut hasher = DefaultHasher::new();
+            self.run(lang, true)?.stdout.hash(&mut hasher);
+            *counts.entry(hasher.finish()).or_default() += 1;
+        }
+        let most_common = counts.values().max().copied().unwrap_or(0);
+        let differing = (n - most_common) as f64 / n.max(1) as f64;
+        Ok(differing <= threshold)
+    }
+
+    /// Builds `lang` with ThreadSanitizer and runs it on the test inputs
+    /// Fails if the sanitizer reports a data race
+    /// The benchmark is linked against the prebuilt, uninstrumented std, so races inside std
+    /// can be reported as false positives. Building std with the sanitizer
+    /// (`cargo -Zbuild-std`) avoids them, but needs a cargo project and the `rust-src` component
+    pub fn run_tsan(&self, lang: &BenchmarkLanguage) -> Result<std::process::Output, Error> {
+        let out = self.run_sanitized(lang, "thread", "run_tsan")?;
+        let stderr = str::from_utf8(&out.stderr).unwrap_or("");
+        if stderr.contains("WARNING: ThreadSanitizer") {
+            return Err(Error::tsan(&self.name, lang, stderr));
+        }
+        Ok(out)
+    }
+
+    /// Builds `lang` with AddressSanitizer and runs it on the test inputs
+    /// Fails if the sanitizer reports any memory errors
+    pub fn run_asan(&self, lang: &BenchmarkLanguage) -> Result<std::process::Output, Error> {
+        let out = self.run_sanitized(lang, "address", "run_asan")?;
+        let stderr = str::from_utf8(&out.stderr).unwrap_or("");
+        if stderr.contains("ERROR: AddressSanitizer") {
+            return Err(Error::asan(&self.name, lang, stderr));
+        }
+        Ok(out)
+    }
+
+    /// Runs the Rust implementation on the test inputs under Miri
+    /// The source is wrapped in a generated cargo project, since Miri only works through cargo
+    pub fn run_miri(&self) -> Result<std::process::Output, Error> {
+        let lang = BenchmarkLanguage::Rust;
+        if !self.languages.contains(&lang) {
+            return Err(Error::unsupported_lang("run_miri", &lang));
+        }
+
+        let mut source_path = self.base_path.join(&self.source_name);
+        source_path.set_extension(lang.ext());
+        let project = PathBuf::from("target_scc")
+            .join("miri_workspace")
+            .join(&self.name);
+        let src_dir = project.join("src");
+        create_dir_all(&src_dir)
+            .map_err(|err| Error::file_access(&src_dir, "create miri project", err))?;
+
+        // plain rustc (as used by compile_cmd) defaults to the 2015 edition
+        let manifest = format!(
+            "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2015\"\n\n[workspace]\n",
+            self.name.to_lowercase()
+        );
+        let manifest_path = project.join("Cargo.toml");
+        write(&manifest_path, manifest)
+            .map_err(|err| Error::file_access(&manifest_path, "write miri manifest", err))?;
+        let main_path = src_dir.join("main.rs");
+        copy(&source_path, &main_path)
+            .map_err(|err| Error::file_access(&main_path, "copy rust source", err))?;
+
+        let mut cmd = Command::new("cargo");
+        cmd.args(["+nightly", "miri", "run", "--"]);
+        cmd.args(&self.config.test_args);
+        cmd.current_dir(&project);
+        let out = cmd
+            .output()
+            .map_err(|err| Error::run(&self.name, &lang, err))?;
+        if !out.status.success() {
+            return Err(Error::run(
+                &self.name,
+                &lang,
+                str::from_utf8(&out.stderr).unwrap_or("Miri exited with nonzero exit status"),
+            ));
+        }
+        Ok(out)
+    }
+
+    fn run_sanitized(
+        &self,
+        lang: &BenchmarkLanguage,
+        sanitizer: &str,
+        operation: &str,
+    ) -> Result<std::process::Output, Error> {
+        if *lang != BenchmarkLanguage::Rust {
+            return Err(Error::unsupported_lang(operation, lang));
+        }
+        let bin_path = self.compile_sanitized(lang, sanitizer)?;
+        let mut cmd = Command::new(&bin_path);
+        cmd.args(&self.config.test_args);
+        cmd.output()
+            .map_err(|err| Error::run(&self.name, lang, err))
+    }
+
+    // sanitizers need a nightly rustc, none of the other compilers support them
+    fn compile_sanitized(
+        &self,
+        lang: &BenchmarkLanguage,
+        sanitizer: &str,
+    ) -> Result<PathBuf, Error> {
+        let mut source_path = self.base_path.join(&self.source_name);
+        source_path.set_extension(lang.ext());
+        let mut bin_path = self.bin_path(lang)?.into_os_string();
+        bin_path.push(format!("_{sanitizer}san"));
+        let bin_path = PathBuf::from(bin_path);
+
+        let mut cmd = Command::new("rustc");
+        cmd.arg("+nightly");
+        cmd.arg(&source_path);
+        cmd.arg("-o");
+        cmd.arg(&bin_path);
+        cmd.arg(format!("-Zsanitizer={sanitizer}"));
+        if sanitizer == "thread" {
+            // nightly refuses to link a thread-sanitized crate against the uninstrumented std
+            cmd.arg("-Cunsafe-allow-abi-mismatch=sanitizer");
+        }
+        cmd.args(["-C", "opt-level=1", "-g", "-Awarnings"]);
+        let out = cmd
+            .output()
+            .map_err(|err| Error::compile(&self.name, lang, "", &err.to_string()))?;
+        if !out.status.success() {
+            return Err(Error::compile(
+                &self.name,
+                lang,
+                str::from_utf8(&out.stdout).unwrap_or(""),
+                str::from_utf8(&out.stderr).unwrap_or(""),
+            ));
+        }
+        Ok(bin_path)
+    }
+
+    /// Counts the instructions executed by `lang` on the benchmark inputs using callgrind
+    /// Unlike wall-clock times, these do not depend on CPU speed or scheduling
+    pub fn compute_instruction_count(
+        &self,
+        lang: &BenchmarkLanguage,
+    ) -> Result<InstructionCountResult, Error> {
+        let out_dir = PathBuf::from("target_scc").join("callgrind");
+        create_dir_all(&out_dir)
+            .map_err(|err| Error::file_access(&out_dir, "create callgrind dir", err))?;
+        let out_file = out_dir.join(format!("{}_{}.out", self.name, lang.suffix()));
+
+        let mut valgrind = Command::new("valgrind");
+        valgrind.arg("--tool=callgrind");
+        valgrind.arg(format!("--callgrind-out-file={}", out_file.display()));
+        let out = self
+            .wrap_cmd(valgrind, lang, &self.config.args)?
+            .output()
+            .map_err(|err| Error::external_tool("valgrind", err))?;
+        if !out.status.success() {
+            return Err(Error::run(
+                &self.name,
+                lang,
+                str::from_utf8(&out.stderr).unwrap_or("Command exited with nonzero exit status"),
+            ));
+        }
+
+        InstructionCountResult::from_callgrind_output(&out_file)
+    }
+
+    /// Instruction counts of all languages, sorted from fewest to most instructions
+    pub fn compare_instruction_counts(&self) -> Result<Vec<InstructionCountResult>, Error> {
+        let mut results = vec![];
+        for lang in self.languages.iter() {
+            results.push(self.compute_instruction_count(lang)?);
+        }
+        results.sort_by_key(|res| res.instructions);
+        Ok(results)
+    }
+
+    /// Profiles `lang` with `flamegraph` (perf) and writes the flame graph to
+    /// `PLOTS_PATH/<name>_flamegraph_<suffix>.svg`
+    pub fn export_flamegraph(&self, lang: &BenchmarkLanguage) -> Result<PathBuf, Error> {
+        if !on_path("flamegraph") {
+            return Err(Error::missing_tool("flamegraph"));
+        }
+        let out_path = self.plot_path(&format!("flamegraph_{}", lang.suffix()))?;
+        let mut flamegraph = Command::new("flamegraph");
+        flamegraph.arg("--output");
+        flamegraph.arg(&out_path);
+        flamegraph.arg("--");
+        let out = self
+            .wrap_cmd(flamegraph, lang, &self.config.args)?
+            .output()
+            .map_err(|err| Error::external_tool("flamegraph", err))?;
+        if !out.status.success() {
+            return Err(Error::run(
+                &self.name,
+                lang,
+                str::from_utf8(&out.stderr).unwrap_or("Command exited with nonzero exit status"),
+            ));
+        }
+        Ok(out_path)
+    }
+
+    /// Counts hardware events of a single run of `lang` with `perf stat`, the counters
+    /// are saved as csv to `RAW_PATH/<name>_<suffix>_perf.csv`
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(benchmark = %self.name, lang = %lang))
+    )]
+    pub fn run_perf_stat(&self, lang: &BenchmarkLanguage) -> Result<PathBuf, Error> {
+        if !on_path("perf") {
+            return Err(Error::missing_tool("perf"));
+        }
+        let out_path =
+            self.result_path()?
+                .with_file_name(format!("{}_{}_perf.csv", self.name, lang.suffix()));
+        let mut perf = Command::new("perf");
+        perf.args(["stat", "-x", ","]);
+        perf.arg("-o");
+        perf.arg(&out_path);
+        perf.arg("--");
+        let mut cmd = self.wrap_cmd(perf, lang, &self.config.args)?;
+        self.set_stdin(&mut cmd)?;
+        let out = cmd
+            .output()
+            .map_err(|err| Error::external_tool("perf", err))?;
+        if !out.status.success() {
+            return Err(Error::run(
+                &self.name,
+                lang,
+                str::from_utf8(&out.stderr).unwrap_or("Command exited with nonzero exit status"),
+            ));
+        }
+        Ok(out_path)
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(benchmark = %self.name))
+    )]
+    pub fn run_hyperfine_all(&self) -> Result<(), Error> {
+        #[cfg(target_os = "linux")]
+        if self.config.perf_stat {
+            for lang in self.languages.iter() {
+                self.run_perf_stat(lang)?;
+            }
+        }
+        if self.config.reproducible_mode {
+            self.warmup_before_hyperfine(self.config.warmup)?;
+        } else {
+            self.run_hyperfine(self.config.warmup, Instant::now(), &[])?;
+        }
+        #[cfg(target_os = "linux")]
+        warn_frequency_spread();
+        if self.config.flamegraph {
+            for lang in self.languages.iter() {
+                let path = self.export_flamegraph(lang)?;
+                println!(
+                    "{} ({lang}) flame graph written to {}",
+                    self.name,
+                    path.display()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Predicts the wall time of `run_hyperfine_all` from the mean run times of the previous
+    /// result csv, or from a single run of every language if there is none
+    /// Timing single runs needs the languages to be compiled
+    pub fn estimate_total_run_time(&self) -> Result<RunTimeEstimate, Error> {
+        let previous = self
+            .result_means_in(&self.result_path()?)
+            .ok()
+            .filter(|means| !means.is_empty());
+        let average_secs = match previous {
+            Some(means) => means.iter().map(|(_, mean)| mean).sum::<f64>() / means.len() as f64,
+            None => {
+                let mut total_secs = 0.0;
+                for lang in self.languages.iter() {
+                    let start = Instant::now();
+                    self.run(lang, false)?;
+                    total_secs += start.elapsed().as_secs_f64();
+                }
+                total_secs / self.languages.len().max(1) as f64
+            }
+        };
+        // every language runs once per scenario, warmup runs take as long as timed ones
+        let runs = (self.config.runs + self.config.warmup) as usize
+            * self.languages.len()
+            * self.config.scenarios.len().max(1);
+        Ok(RunTimeEstimate(Duration::from_secs_f64(
+            average_secs * runs as f64,
+        )))
+    }
+
+    /// Fails if the installed hyperfine is older than the features used by the config need
+    /// The json export needs 1.14, `--input` 1.17 and pinning to cores 1.18
+    /// If the version cannot be read, running hyperfine reports the actual problem
+    pub fn check_hyperfine_version(&self) -> Result<(), Error> {
+        let Some(found) = hyperfine_version()
+            .ok()
+            .and_then(|version| parse_version(&version))
+        else {
+            return Ok(());
+        };
+        let (bench_cores, hyperfine_core) = self.affinity();
+        // newest requirement first, so the error names the version that fixes everything
+        let requirements = [
+            (
+                "cpu affinity",
+                bench_cores != AffinityStrategy::None || hyperfine_core.is_some(),
+                (1, 18, 0),
+            ),
+            ("stdin input", self.stdin_file()?.is_some(), (1, 17, 0)),
+            ("json export", true, (1, 14, 0)),
+        ];
+        for (feature, used, required) in requirements {
+            if used && found < required {
+                return Err(Error::hyperfine_too_old(feature, required, found));
+            }
+        }
+        Ok(())
+    }
+
+    /// Cores the benchmarks and hyperfine itself are pinned to
+    /// In reproducible mode, unset cores default to the last core for benchmarks
+    /// and the first core for hyperfine, so they do not compete with each other
+    pub fn affinity(&self) -> (AffinityStrategy, Option<u32>) {
+        let (mut bench_cores, mut hyperfine_core) = (
+            self.config.affinity_strategy.clone(),
+            self.config.hyperfine_affinity,
+        );
+        let cores = available_parallelism() as u32;
+        if self.config.reproducible_mode && cores > 1 {
+            if bench_cores == AffinityStrategy::None {
+                bench_cores = AffinityStrategy::SingleCore(cores - 1);
+            }
+            hyperfine_core = hyperfine_core.or(Some(0));
+        }
+        (bench_cores, hyperfine_core)
+    }
+
+    /// Runs `run_hyperfine_all` of every benchmark on `concurrency` threads,
+    /// one per available core by default
+    /// Returns the first error after all started benchmarks have finished
+    pub fn run_suite_parallel(
+        benchmarks: &[Benchmark],
+        concurrency: Option<usize>,
+    ) -> Result<(), Error> {
+        let concurrency = concurrency.unwrap_or_else(available_parallelism);
+        let next = AtomicUsize::new(0);
+        let errors = Mutex::new(vec![]);
+        std::thread::scope(|scope| {
+            for _ in 0..concurrency.clamp(1, benchmarks.len().max(1)) {
+                scope.spawn(|| {
+                    while let Some(bench) = benchmarks.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        if let Err(err) = bench.run_hyperfine_all() {
+                            errors
+                                .lock()
+                                .unwrap_or_else(|err| err.into_inner())
+                                .push(err);
+                        }
+                    }
+                });
+            }
+        });
+        match errors
+            .into_inner()
+            .unwrap_or_else(|err| err.into_inner())
+            .into_iter()
+            .next()
+        {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Runs every language `warmup_runs` times per scenario before benchmarking it with
+    /// hyperfine, which then skips its own warmup runs
+    /// Unless `strict` is set, languages failing a warmup run are left out of the benchmark
+    pub fn warmup_before_hyperfine(&self, warmup_runs: u32) -> Result<(), Error> {
+        let start = Instant::now();
+        let arg_sets: Vec<&[String]> = if self.config.scenarios.is_empty() {
+            vec![&self.config.args]
+        } else {
+            self.config
+                .scenarios
+                .iter()
+                .map(|(_, args)| args.as_slice())
+                .collect()
+        };
+        let mut failed = vec![];
+        for lang in self.languages.iter() {
+            let warmup = arg_sets.iter().try_for_each(|args| {
+                (0..warmup_runs).try_for_each(|_| self.run_with_args(lang, args).map(|_| ()))
+            });
+            match warmup {
+                Ok(()) => {}
+                Err(err) if self.config.strict => return Err(err),
+                Err(_) => {
+                    println!("{} ({lang}) failed during warmup, skipping", self.name);
+                    failed.push(*lang);
+                }
+            }
+        }
+        self.run_hyperfine(0, start, &failed)
+    }
+
+    /// `start` is the time the whole run began, including any manual warmup
+    /// `skipped` languages are recorded as failed without being run
+    fn run_hyperfine(
+        &self,
+        warmup: u32,
+        start: Instant,
+        skipped: &[BenchmarkLanguage],
+    ) -> Result<(), Error> {
+        self.check_hyperfine_version()?;
+        #[cfg(target_os = "linux")]
+        warn_scaling_governor();
+        #[cfg(unix)]
+        warn_fd_limit();
+        let (bench_cores, hyperfine_core) = self.affinity();
+        let taskset_arg = bench_cores.to_taskset_arg();
+        let pinned = |call_str: String| match &taskset_arg {
+            Some(cores) => format!("taskset {cores} {call_str}"),
+            None => call_str,
+        };
+        if let Some(setup) = &self.config.setup_cmd {
+            run_shell(setup)?;
+        }
+        let _teardown = TeardownGuard(self.config.teardown_cmd.as_deref());
+
         let mut commands: Vec<String> = Vec::with_capacity(self.languages.len());
+        let mut names: Vec<String> = vec![];
+        let mut suffixes: Vec<&str> = vec![];
+        let mut failed: Vec<String> = vec![];
         for lang in self.languages.iter() {
             if !self.languages.contains(lang) {
                 return Err(Error::unknown_lang("Run Hyperfine", lang));
             }
 
-            let bin_path = self.bin_path(lang)?;
-            let path_err = Error::path_access(&bin_path, "Path as String");
+            if skipped.contains(lang) {
+                failed.push(format!(
+                    "{},{},failed",
+                    self.call_str(lang, &self.config.args)?,
+                    lang.suffix()
+                ));
+                continue;
+            }
+            // drop languages that crash on the test inputs, so hyperfine still runs the others
+            if !self.config.strict
+                && !self
+                    .run_outcome(lang, None, &self.config.test_args)?
+                    .success()
+            {
+                println!("{} ({lang}) failed on test inputs, skipping", self.name);
+                failed.push(format!(
+                    "{},{},failed",
+                    self.call_str(lang, &self.config.args)?,
+                    lang.suffix()
+                ));
+                continue;
+            }
 
-            let bin_str = bin_path.to_str().ok_or(path_err)?.to_owned();
-            let mut call_str = if *lang == BenchmarkLanguage::SmlNj {
-                format!("sml @SMLload {bin_str}")
-            } else {
-                bin_str
-            };
-            for arg in &self.config.args {
-                call_str.push(' ');
-                call_str.push_str(arg);
+            if self.config.scenarios.is_empty() {
+                commands.push(pinned(self.call_str(lang, &self.config.args)?));
+                suffixes.push(lang.suffix());
+                continue;
             }
+            for (label, args) in self.config.scenarios.iter() {
+                commands.push(pinned(self.call_str(lang, args)?));
+                names.push(format!("{}/{label}", lang.suffix()));
+            }
+        }
 
-            commands.push(call_str)
+        let failed_path = self.failed_path()?;
+        if !failed.is_empty() {
+            let contents = format!("command,lang,status\n{}\n", failed.join("\n"));
+            write(&failed_path, contents)
+                .map_err(|err| Error::file_access(&failed_path, "write failed languages", err))?;
+        } else if failed_path.exists() {
+            remove_file(&failed_path)
+                .map_err(|err| Error::file_access(&failed_path, "remove stale failures", err))?;
         }
 
-        let out_path = self.result_path()?;
+        if !fits_command_line(&commands) {
+            println!(
+                "{} commands exceed the argument size limit, running them from a script",
+                self.name
+            );
+            let script_path = self.commands_script_path()?;
+            self.write_commands_script(&script_path, &commands)?;
+            let script = shell_quote(&script_path.to_string_lossy());
+            commands = (0..commands.len())
+                .map(|ind| format!("sh {script} {ind}"))
+                .collect();
+            // the generated commands no longer contain the call strings results are matched on
+            if names.is_empty() {
+                names = suffixes.iter().map(|suffix| suffix.to_string()).collect();
+            }
+        }
+
+        let out_path = if self.config.archive {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since| since.as_secs());
+            self.archive_result_path(timestamp)?
+        } else {
+            self.result_path()?
+        };
 
-        let mut command = Command::new("hyperfine");
+        let mut command = match hyperfine_core {
+            Some(core) => {
+                let mut taskset = Command::new("taskset");
+                taskset.args(["-c", &core.to_string(), "hyperfine"]);
+                taskset
+            }
+            None => Command::new("hyperfine"),
+        };
+        // hyperfine passes its environment on to the benchmarks
+        self.apply_env_passthrough(&mut command);
+        if self.config.set_malloc_arena_max {
+            command.env("MALLOC_ARENA_MAX", "1");
+        }
         command.args(commands);
+        for name in names {
+            command.arg("--command-name");
+            command.arg(name);
+        }
         command.arg("--runs");
         command.arg(self.config.runs.to_string());
-        command.args(["--warmup", "3"]);
+        command.arg("--warmup");
+        command.arg(warmup.to_string());
+        if !self.config.strict {
+            command.arg("--ignore-failure");
+        }
+        if self.config.show_output {
+            command.arg("--show-output");
+        }
+        if let Some(cleanup) = &self.config.cleanup_cmd {
+            command.arg("--cleanup");
+            command.arg(cleanup);
+        }
+        if let Some(stdin_file) = self.stdin_file()? {
+            command.arg("--input");
+            command.arg(stdin_file);
+        }
+        // global hyperfine options, e.g. `HYPERFINE_ARGS="--time-unit millisecond"`
+        if let Ok(extra_args) = env::var("HYPERFINE_ARGS") {
+            command.args(split_args(&extra_args));
+        }
         command.arg("--export-csv");
         command.arg(&out_path);
+        command.arg("--export-json");
+        command.arg(self.json_result_path()?);
         println!("hyperfine command: {command:?}");
-        command
+        let status = command
             .status()
             .map_err(|err| Error::hyperfine(&self.name, err))?;
+        // failing benchmarks are ignored unless strict, so this is a failing benchmark in
+        // strict mode or hyperfine itself failing
+        if !status.success() {
+            return Err(Error::hyperfine(
+                &self.name,
+                std::io::Error::other(format!("hyperfine exited with {status}")),
+            ));
+        }
+        if self.config.archive {
+            self.link_latest_result(&out_path)?;
+            // `<name>.csv` is what the report and everything reading `result_path` expect
+            let result_path = self.result_path()?;
+            copy(&out_path, &result_path)
+                .map_err(|err| Error::file_access(&result_path, "copy latest result", err))?;
+        }
+        let columns_path = self.columns_path()?;
+        write(&columns_path, Benchmark::export_csv_column_descriptions())
+            .map_err(|err| Error::file_access(&columns_path, "write column descriptions", err))?;
+
+        let elapsed = start.elapsed();
+        println!("{} finished in {:.1}s", self.name, elapsed.as_secs_f64());
+        self.write_metadata_json(&Metadata {
+            benchmark: self.name.clone(),
+            hyperfine_version: hyperfine_version().ok(),
+            reproducible_mode: self.config.reproducible_mode,
+            run_elapsed_secs: elapsed.as_secs_f64(),
+            processor: Benchmark::detect_processor_info(),
+        })?;
+        if self.config.print_summary {
+            self.print_summary(&out_path)?;
+        }
+
+        Ok(())
+    }
+
+    /// Script generated for command lists that are too long to pass to hyperfine
+    pub fn commands_script_path(&self) -> Result<PathBuf, Error> {
+        create_dir_all(RAW_PATH)
+            .map_err(|_| Error::path_access(&PathBuf::from(RAW_PATH), "create hyperfine path"))?;
+        let mut path = PathBuf::from(RAW_PATH).join(format!("{}_commands", self.name));
+        path.set_extension("sh");
+        Ok(path)
+    }
+
+    /// Writes a script that runs the `n`th command when called with `n`, one command per line
+    fn write_commands_script(&self, path: &Path, commands: &[String]) -> Result<(), Error> {
+        let mut script = String::from("#!/bin/sh\ncase \"$1\" in\n");
+        for (ind, cmd) in commands.iter().enumerate() {
+            script.push_str(&format!("{ind}) exec {cmd} ;;\n"));
+        }
+        script.push_str("*) echo \"unknown command $1\" >&2; exit 1 ;;\nesac\n");
+        write(path, script).map_err(|err| Error::file_access(path, "write commands script", err))
+    }
 
+    /// Prints the mean run time of every language in the result csv at `path`,
+    /// fastest first and relative to the fastest
+    fn print_summary(&self, path: &Path) -> Result<(), Error> {
+        let mut means = self.result_means_in(path)?;
+        means.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+        let Some((_, fastest)) = means.first().copied() else {
+            return Ok(());
+        };
+        let width = means
+            .iter()
+            .map(|(lang, _)| lang.to_string().len())
+            .max()
+            .unwrap_or(0);
+        println!("Summary of {}:", self.name);
+        for (lang, mean) in means {
+            println!(
+                "  {:<width$}  {:>10}  {:>6.2}x",
+                lang.to_string(),
+                format_secs(mean),
+                mean / fastest
+            );
+        }
         Ok(())
     }
 
+    /// Commands and their individual run times (in seconds) from the json export
+    fn json_run_times(&self) -> Result<Vec<(String, Vec<f64>)>, Error> {
+        let path = self.json_result_path()?;
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|err| Error::file_access(&path, "read json results", err))?;
+        let json: serde_json::Value = serde_json::from_str(&contents)
+            .map_err(|err| Error::file_access(&path, "parse json results", err))?;
+
+        Ok(json["results"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|result| {
+                let command = result["command"].as_str().unwrap_or("").to_owned();
+                let times = result["times"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|time| time.as_f64())
+                    .collect();
+                (command, times)
+            })
+            .collect())
+    }
+
+    /// Individual run times (in seconds) of `lang` from the json export
+    /// For benchmarks with scenarios, the times of the first scenario are returned
+    pub fn run_times(&self, lang: &BenchmarkLanguage) -> Result<Vec<f64>, Error> {
+        let call_str = self.call_str(lang, &self.config.args)?;
+        let scenario_prefix = format!("{}/", lang.suffix());
+        self.json_run_times()?
+            .into_iter()
+            // commands pinned with taskset have a prefix, commands run from a script are named
+            // by their suffix
+            .find(|(cmd, _)| {
+                cmd.ends_with(&call_str)
+                    || cmd.starts_with(&scenario_prefix)
+                    || cmd == lang.suffix()
+            })
+            .map(|(_, times)| times)
+            .ok_or(Error::missing_lang(*lang))
+    }
+
+    /// Indices of the runs of each language that are more than `sigma_threshold` standard
+    /// deviations away from the mean, many outliers hint at throttling or scheduler noise
+    pub fn detect_outlier_runs(
+        &self,
+        sigma_threshold: f64,
+    ) -> Result<HashMap<BenchmarkLanguage, Vec<usize>>, Error> {
+        let mut outliers = HashMap::new();
+        for lang in self.languages.iter() {
+            // languages dropped from the hyperfine run have no times
+            let times = match self.run_times(lang) {
+                Err(Error::MissingData { .. }) => continue,
+                times => times?,
+            };
+            if times.len() < 2 {
+                continue;
+            }
+            let (mean, std_dev) = (mean(&times), variance(&times).sqrt());
+            let indices = times
+                .iter()
+                .enumerate()
+                .filter(|(_, time)| (*time - mean).abs() > sigma_threshold * std_dev)
+                .map(|(ind, _)| ind)
+                .collect();
+            outliers.insert(*lang, indices);
+        }
+        Ok(outliers)
+    }
+
+    /// Mean run time (in seconds) of each language from the result csv
+    pub fn result_means(&self) -> Result<Vec<(BenchmarkLanguage, f64)>, Error> {
+        self.result_means_in(&self.result_path()?)
+    }
+
+    /// Mean run time (in seconds) of each language from the hyperfine csv at `path`
+    /// For benchmarks with scenarios, this is the geometric mean over all scenarios
+    fn result_means_in(&self, path: &Path) -> Result<Vec<(BenchmarkLanguage, f64)>, Error> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| Error::file_access(path, "read results", err))?;
+        let mut means = vec![];
+        for lang in self.languages.iter() {
+            let scenario_means: Vec<f64> = self
+                .result_rows(&contents, lang)?
+                .into_iter()
+                .filter_map(|(_, values)| values.first().copied())
+                .collect();
+            if !scenario_means.is_empty() {
+                means.push((*lang, geometric_mean(&scenario_means)));
+            }
+        }
+        Ok(means)
+    }
+
+    /// Rows of `lang` in the hyperfine csv `contents`
+    /// Rows are matched on the call string, or on the `<suffix>` and `<suffix>/<label>`
+    /// command names used for scenarios and commands run from a script
+    fn result_rows<'a>(
+        &self,
+        contents: &'a str,
+        lang: &BenchmarkLanguage,
+    ) -> Result<Vec<ResultRow<'a>>, Error> {
+        let call_str = self.call_str(lang, &self.config.args)?;
+        let scenario_prefix = format!("{}/", lang.suffix());
+        Ok(contents
+            .lines()
+            .skip(1)
+            .filter_map(|line| {
+                let (command, rest) = line.split_once(',')?;
+                let label = command.strip_prefix(&scenario_prefix);
+                if label.is_none() && !command.ends_with(&call_str) && command != lang.suffix() {
+                    return None;
+                }
+                let values = rest
+                    .split(',')
+                    .map(|value| value.parse::<f64>().ok())
+                    .collect::<Option<Vec<f64>>>()?;
+                Some((label, values))
+            })
+            .collect())
+    }
+
+    /// Change of the mean run time of every language between two result csvs
+    /// Languages missing from either file are left out
+    pub fn diff_results(
+        &self,
+        baseline_path: &Path,
+        new_path: &Path,
+    ) -> Result<Vec<ResultDiff>, Error> {
+        let baseline = self.result_means_in(baseline_path)?;
+        let new = self.result_means_in(new_path)?;
+        Ok(baseline
+            .into_iter()
+            .filter_map(|(lang, baseline_mean)| {
+                let (_, new_mean) = new.iter().find(|(new_lang, _)| *new_lang == lang)?;
+                Some(ResultDiff {
+                    lang,
+                    baseline_mean,
+                    new_mean: *new_mean,
+                    change_pct: (new_mean - baseline_mean) / baseline_mean * 100.0,
+                })
+            })
+            .collect())
+    }
+
+    /// Geometric mean of the mean run times of `lang` in the result csvs of `benchmarks`,
+    /// the usual summary of a language's performance over a whole suite
+    /// Benchmarks not implemented in `lang` are left out
+    pub fn geometric_mean_across_suite(
+        benchmarks: &[Benchmark],
+        lang: &BenchmarkLanguage,
+    ) -> Result<f64, Error> {
+        let mut means = vec![];
+        for bench in benchmarks
+            .iter()
+            .filter(|bench| bench.languages.contains(lang))
+        {
+            let mean = bench
+                .result_means_in(&bench.result_path()?)?
+                .into_iter()
+                .find(|(result_lang, _)| result_lang == lang)
+                .ok_or(Error::missing_lang(*lang))?
+                .1;
+            means.push(mean);
+        }
+        if means.is_empty() {
+            return Err(Error::missing_lang(*lang));
+        }
+        Ok(geometric_mean(&means))
+    }
+
+    /// Markdown table comparing two result csvs, e.g. for PR comments
+    /// Languages that got slower by more than `threshold_pct` percent are marked with ❌,
+    /// slower ones within the threshold with ⚠️ and unchanged or faster ones with ✅
+    pub fn export_comparison_regression_md(
+        &self,
+        baseline_path: &Path,
+        new_path: &Path,
+        threshold_pct: f64,
+    ) -> Result<String, Error> {
+        let diffs = self.diff_results(baseline_path, new_path)?;
+        let mut md = format!(
+            "### {}\n\n| | Language | Baseline [s] | New [s] | Change |\n|---|---|---:|---:|---:|\n",
+            self.name
+        );
+        for diff in diffs {
+            let status = if diff.change_pct > threshold_pct {
+                "❌"
+            } else if diff.change_pct > 0.0 {
+                "⚠️"
+            } else {
+                "✅"
+            };
+            md.push_str(&format!(
+                "| {status} | {} | {:.4} | {:.4} | {:+.1}% |\n",
+                diff.lang, diff.baseline_mean, diff.new_mean, diff.change_pct
+            ));
+        }
+        Ok(md)
+    }
+
+    /// Badge with the change of the mean run time of the fastest language compared to the
+    /// result csv at `baseline_path`, written to `PLOTS_PATH/<name>_trend_badge.svg`
+    pub fn export_timing_regression_badge(&self, baseline_path: &Path) -> Result<(), Error> {
+        let result_path = self.result_path()?;
+        let means = self.result_means_in(&result_path)?;
+        let (fastest, _) = means
+            .iter()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .ok_or(Error::path_access(&result_path, "find fastest language"))?;
+        let diff = self
+            .diff_results(baseline_path, &result_path)?
+            .into_iter()
+            .find(|diff| diff.lang == *fastest)
+            .ok_or(Error::missing_lang(*fastest))?;
+
+        let (message, color) = if diff.change_pct <= 0.0 {
+            (format!("⚡ {:+.0}%", diff.change_pct), "#2e7d32")
+        } else {
+            (format!("🐢 {:+.0}%", diff.change_pct), "#c62828")
+        };
+        let path = self.plot_path("trend_badge")?;
+        write(&path, svg::badge(&fastest.to_string(), &message, color))
+            .map_err(|err| Error::file_access(&path, "write badge", err))
+    }
+
+    /// Badge naming the language with the smallest binary, e.g. "smallest: Rust (1.2MB)",
+    /// written to `PLOTS_PATH/<name>_size_badge.svg`
+    pub fn export_size_badge_svg(&self) -> Result<(), Error> {
+        let sizes = self.compare_binary_sizes()?;
+        let Some((smallest, size)) = sizes.first() else {
+            return Err(Error::path_access(
+                &self.base_path,
+                "find compiled binaries",
+            ));
+        };
+        let size = if *size >= 1024 * 1024 {
+            format!("{:.1}MB", *size as f64 / (1024.0 * 1024.0))
+        } else {
+            format!("{:.1}KB", *size as f64 / 1024.0)
+        };
+        let path = self.plot_path("size_badge")?;
+        write(
+            &path,
+            svg::badge("smallest", &format!("{smallest} ({size})"), "#1b4253"),
+        )
+        .map_err(|err| Error::file_access(&path, "write badge", err))
+    }
+
+    /// Results in the json format of Airspeed Velocity (version 2), with one benchmark named
+    /// `<name>.<suffix>` per language, so they can be shown on an ASV dashboard
+    pub fn export_asv_results(&self, commit_hash: &str) -> Result<String, Error> {
+        let path = self.result_path()?;
+        let started_at = metadata(&path)
+            .and_then(|meta| meta.modified())
+            .map_err(|err| Error::file_access(&path, "read result metadata", err))?
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_millis() as u64);
+        let processor = Benchmark::detect_processor_info();
+
+        let mut results = serde_json::Map::new();
+        for (lang, mean) in self.result_means_in(&path)? {
+            results.insert(
+                format!("{}.{}", self.name, lang.suffix()),
+                serde_json::json!([[mean], [], null, started_at, mean * self.config.runs as f64]),
+            );
+        }
+        let asv = serde_json::json!({
+            "version": 2,
+            "commit_hash": commit_hash,
+            "env_name": "hyperfine",
+            "date": started_at,
+            "params": {
+                "machine": env::var("HOSTNAME").unwrap_or_default(),
+                "cpu": processor.model_name,
+                "num_cpu": processor.logical_threads.to_string(),
+                "os": env::consts::OS,
+                "arch": env::consts::ARCH,
+            },
+            "python": "",
+            "requirements": {},
+            "env_vars": {},
+            "result_columns": ["result", "params", "version", "started_at", "duration"],
+            "results": results,
+            "durations": {},
+        });
+        serde_json::to_string_pretty(&asv).map_err(|err| Error::external_tool("asv", err))
+    }
+
+    /// Appends a Markdown table of the results to the file in `GITHUB_STEP_SUMMARY`,
+    /// so they show up on the summary page of the GitHub Actions run
+    /// Does nothing outside of GitHub Actions
+    pub fn export_github_actions_summary(&self) -> Result<(), Error> {
+        let Ok(summary_path) = env::var("GITHUB_STEP_SUMMARY") else {
+            return Ok(());
+        };
+        let path = self.result_path()?;
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|err| Error::file_access(&path, "read results", err))?;
+
+        let mut table = format!(
+            "### {}\n\n| Language | Mean [s] | Std. dev. [s] | Min [s] | Max [s] |\n|---|---:|---:|---:|---:|\n",
+            self.name
+        );
+        for lang in self.languages_sorted() {
+            // hyperfine csv columns: command,mean,stddev,median,user,system,min,max
+            for (label, values) in self.result_rows(&contents, &lang)? {
+                let name = match label {
+                    Some(label) => format!("{lang} ({label})"),
+                    None => lang.to_string(),
+                };
+                if let [mean, stddev, _, _, _, min, max, ..] = values[..] {
+                    table.push_str(&format!(
+                        "| {name} | {mean:.4} | {stddev:.4} | {min:.4} | {max:.4} |\n"
+                    ));
+                }
+            }
+        }
+        table.push('\n');
+
+        let summary_path = PathBuf::from(summary_path);
+        let mut summary = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&summary_path)
+            .map_err(|err| Error::file_access(&summary_path, "open step summary", err))?;
+        std::io::Write::write_all(&mut summary, table.as_bytes())
+            .map_err(|err| Error::file_access(&summary_path, "write step summary", err))
+    }
+
+    /// Prints a GitHub Actions `::warning` workflow command for every regression, which
+    /// shows up as an annotation on the source file of the language in pull requests
+    /// Returns the printed commands
+    pub fn export_ci_annotations(&self, regressions: &[ResultDiff]) -> Result<Vec<String>, Error> {
+        let mut annotations = Vec::with_capacity(regressions.len());
+        for diff in regressions {
+            if !self.languages.contains(&diff.lang) {
+                return Err(Error::missing_lang(diff.lang));
+            }
+            let annotation = format!(
+                "::warning file={},title=Performance Regression::{} is {:.1}% slower",
+                self.source_path(&diff.lang).display(),
+                diff.lang,
+                diff.change_pct
+            );
+            println!("{annotation}");
+            annotations.push(annotation);
+        }
+        Ok(annotations)
+    }
+
+    /// Html table of the speedup of every language relative to Compiling-SC,
+    /// with one row per benchmark, written to `PLOTS_PATH/heatmap.html`
+    pub fn export_comparison_heatmap_html(benchmarks: &[Benchmark]) -> Result<String, Error> {
+        let langs: Vec<BenchmarkLanguage> = BenchmarkLanguage::all()
+            .into_iter()
+            .filter(|lang| *lang != BenchmarkLanguage::Scc)
+            .filter(|lang| {
+                benchmarks
+                    .iter()
+                    .any(|bench| bench.languages.contains(lang))
+            })
+            .collect();
+
+        let mut rows = String::new();
+        for bench in benchmarks {
+            let means = bench.result_means()?;
+            let mean_of = |lang: BenchmarkLanguage| {
+                means
+                    .iter()
+                    .find(|(other, _)| *other == lang)
+                    .map(|(_, mean)| *mean)
+            };
+            let baseline = mean_of(BenchmarkLanguage::Scc);
+            rows.push_str(&format!("<tr><th>{}</th>", html::escape(&bench.name)));
+            for lang in langs.iter() {
+                match (baseline, mean_of(*lang)) {
+                    (Some(baseline), Some(mean)) => {
+                        let speedup = baseline / mean;
+                        rows.push_str(&format!(
+                            "<td style=\"background: {}\">{speedup:.2}x</td>",
+                            heatmap_color(speedup)
+                        ));
+                    }
+                    _ => rows.push_str("<td></td>"),
+                }
+            }
+            rows.push_str("</tr>\n");
+        }
+
+        let header: String = langs
+            .iter()
+            .map(|lang| format!("<th>{lang}</th>"))
+            .collect();
+        let body = format!(
+            "<h1>Speedup relative to {}</h1>\n<table>\n<tr><th></th>{header}</tr>\n{rows}</table>",
+            BenchmarkLanguage::Scc
+        );
+        let style = "table { border-collapse: collapse; font-family: sans-serif; }\n\
+                     th, td { padding: 0.3em 0.8em; border: 1px solid #ddd; text-align: right; }";
+        let html = html::page("Benchmark heatmap", style, &body);
+
+        create_dir_all(PLOTS_PATH)
+            .map_err(|_| Error::path_access(&PathBuf::from(PLOTS_PATH), "create plots path"))?;
+        let path = PathBuf::from(PLOTS_PATH).join("heatmap.html");
+        write(&path, &html).map_err(|err| Error::file_access(&path, "write heatmap", err))?;
+        Ok(html)
+    }
+
+    /// Html table with the syscall counts of every language side by side, written to
+    /// `PLOTS_PATH/<name>_syscalls.html`. Syscalls are sorted by their total number of calls
+    #[cfg(target_os = "linux")]
+    pub fn export_syscall_comparison_html(&self) -> Result<PathBuf, Error> {
+        let mut results = vec![];
+        for lang in self.languages_sorted() {
+            results.push(self.run_with_strace(&lang, &[])?);
+        }
+
+        let mut totals: HashMap<&str, u64> = HashMap::new();
+        for res in results.iter() {
+            for (name, count) in res.syscall_counts.iter() {
+                *totals.entry(name).or_default() += count;
+            }
+        }
+        let mut syscalls: Vec<(&str, u64)> = totals.into_iter().collect();
+        syscalls.sort_by(|(name_a, a), (name_b, b)| b.cmp(a).then(name_a.cmp(name_b)));
+
+        let mut rows = String::new();
+        for (syscall, _) in syscalls {
+            rows.push_str(&format!("<tr><th>{}</th>", html::escape(syscall)));
+            for res in results.iter() {
+                match res.syscall_counts.get(syscall) {
+                    Some(count) => rows.push_str(&format!("<td>{count}</td>")),
+                    None => rows.push_str("<td></td>"),
+                }
+            }
+            rows.push_str("</tr>\n");
+        }
+        rows.push_str("<tr><th>time [s]</th>");
+        for res in results.iter() {
+            rows.push_str(&format!("<td>{:.6}</td>", res.total_time_secs));
+        }
+        rows.push_str("</tr>\n");
+
+        let header: String = results
+            .iter()
+            .map(|res| format!("<th>{}</th>", res.lang))
+            .collect();
+        let body = format!(
+            "<h1>Syscalls of {}</h1>\n<table>\n<tr><th></th>{header}</tr>\n{rows}</table>",
+            html::escape(&self.name)
+        );
+        let style = "table { border-collapse: collapse; font-family: sans-serif; }\n\
+                     th, td { padding: 0.3em 0.8em; border: 1px solid #ddd; text-align: right; }";
+        let html = html::page(&format!("{} syscalls", self.name), style, &body);
+
+        let path = self.plot_path("syscalls")?.with_extension("html");
+        write(&path, html).map_err(|err| Error::file_access(&path, "write syscall table", err))?;
+        Ok(path)
+    }
+
+    /// Run times of every language that is part of the json export
+    fn run_times_all(&self) -> Result<Vec<(String, Vec<f64>)>, Error> {
+        let mut series = vec![];
+        for lang in self.languages.iter() {
+            // languages dropped from the hyperfine run have no times
+            match self.run_times(lang) {
+                Err(Error::MissingData { .. }) => continue,
+                times => series.push((lang.to_string(), times?)),
+            }
+        }
+        Ok(series)
+    }
+
+    fn plot_path(&self, kind: &str) -> Result<PathBuf, Error> {
+        create_dir_all(PLOTS_PATH)
+            .map_err(|_| Error::path_access(&PathBuf::from(PLOTS_PATH), "create plots path"))?;
+        let mut path = PathBuf::from(PLOTS_PATH).join(format!("{}_{kind}", self.name));
+        path.set_extension("svg");
+        Ok(path)
+    }
+
+    /// Scatter plot of the time of each run against its index,
+    /// which shows warmup effects and timing variability
+    pub fn export_svg_timeline(&self) -> Result<PathBuf, Error> {
+        let series = self.run_times_all()?;
+        let svg = svg::scatter_plot(&format!("{} run times", self.name), "time (s)", &series);
+        let path = self.plot_path("timeline")?;
+        write(&path, svg).map_err(|err| Error::file_access(&path, "write timeline", err))?;
+        Ok(path)
+    }
+
+    /// Box plot of the run times of each language (min, quartiles, median and max)
+    pub fn export_svg_boxplot(&self) -> Result<PathBuf, Error> {
+        let series = self.run_times_all()?;
+        let svg = svg::box_plot(&format!("{} run times", self.name), "time (s)", &series);
+        let path = self.plot_path("boxplot")?;
+        write(&path, svg).map_err(|err| Error::file_access(&path, "write box plot", err))?;
+        Ok(path)
+    }
+
+    /// Sidecar with bootstrap confidence intervals of the mean run time of each command
+    pub fn ci_path(&self) -> Result<PathBuf, Error> {
+        create_dir_all(RAW_PATH)
+            .map_err(|_| Error::path_access(&PathBuf::from(RAW_PATH), "create hyperfine path"))?;
+        let mut path = PathBuf::from(RAW_PATH).join(format!("{}_ci", self.name));
+        path.set_extension("csv");
+        Ok(path)
+    }
+
+    /// Writes the mean run time of each command together with its bootstrap
+    /// confidence interval (e.g. `confidence = 0.95`) to `ci_path`
+    pub fn export_csv_confidence_intervals(&self, confidence: f64) -> Result<(), Error> {
+        if !(confidence > 0.0 && confidence < 1.0) {
+            return Err(Error::parse_config("confidence", &confidence.to_string()));
+        }
+        let mut contents = "command,mean,ci_low,ci_high\n".to_owned();
+        for (command, times) in self.json_run_times()? {
+            if times.is_empty() {
+                continue;
+            }
+            let (ci_low, ci_high) = bootstrap_mean_ci(&times, confidence, 10_000);
+            contents.push_str(&format!("{command},{},{ci_low},{ci_high}\n", mean(&times)));
+        }
+        let path = self.ci_path()?;
+        write(&path, contents)
+            .map_err(|err| Error::file_access(&path, "write confidence intervals", err))
+    }
+
+    /// Welch's t-test on the run times of `lang_a` and `lang_b`, significant at p < 0.05
+    pub fn stats_test_welch(
+        &self,
+        lang_a: &BenchmarkLanguage,
+        lang_b: &BenchmarkLanguage,
+    ) -> Result<WelchResult, Error> {
+        let times_a = self.run_times(lang_a)?;
+        let times_b = self.run_times(lang_b)?;
+        if times_a.len() < 2 {
+            return Err(Error::missing_lang(*lang_a));
+        }
+        if times_b.len() < 2 {
+            return Err(Error::missing_lang(*lang_b));
+        }
+        let (t_statistic, p_value) = welch_t_test(&times_a, &times_b);
+        let alpha = 0.05;
+        Ok(WelchResult {
+            lang_a: lang_a.to_string(),
+            lang_b: lang_b.to_string(),
+            t_statistic,
+            p_value,
+            alpha,
+            significant: p_value < alpha,
+        })
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub fn load_all(
         exclude_lang: &[BenchmarkLanguage],
         exclude_bench: &[String],
     ) -> Result<Vec<Benchmark>, Error> {
-        let mut benchmarks = vec![];
-        let suite_path = PathBuf::from(SUITE_PATH);
-        for path in read_dir(&suite_path).map_err(|err| Error::read_dir(&suite_path, err))? {
-            let path = path
-                .map_err(|_| Error::path_access(&suite_path, "Read File"))?
-                .path();
-            let name = path.file_name().unwrap().to_str().unwrap().to_owned();
-            if !path.is_dir() || exclude_bench.contains(&name) {
-                continue;
+        Benchmark::load_filtered(&BenchmarkFilter::excluding(exclude_lang, exclude_bench))
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn load_filtered(filter: &BenchmarkFilter) -> Result<Vec<Benchmark>, Error> {
+        Benchmark::load_dirs_filtered(&[PathBuf::from(SUITE_PATH)], filter)
+    }
+
+    /// Loads the benchmarks of several suite directories
+    /// Benchmark names have to be unique across all directories
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub fn load_from_dirs(
+        dirs: &[PathBuf],
+        exclude_lang: &[BenchmarkLanguage],
+        exclude_bench: &[String],
+    ) -> Result<Vec<Benchmark>, Error> {
+        Benchmark::load_dirs_filtered(
+            dirs,
+            &BenchmarkFilter::excluding(exclude_lang, exclude_bench),
+        )
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    fn load_dirs_filtered(
+        dirs: &[PathBuf],
+        filter: &BenchmarkFilter,
+    ) -> Result<Vec<Benchmark>, Error> {
+        let mut benchmarks: Vec<Benchmark> = vec![];
+        for suite_path in dirs {
+            for path in read_dir(suite_path).map_err(|err| Error::read_dir(suite_path, err))? {
+                let path = path
+                    .map_err(|_| Error::path_access(suite_path, "Read File"))?
+                    .path();
+                let name = path.file_name().unwrap().to_str().unwrap().to_owned();
+                if !path.is_dir() || !filter.matches_name(&name) {
+                    continue;
+                }
+
+                if let Some(other) = benchmarks.iter().find(|bench| bench.name == name) {
+                    return Err(Error::duplicate_bench(&name, &other.base_path, &path));
+                }
+                let benchmark = Benchmark::new_in(suite_path, &name, &filter.exclude_lang)?;
+                benchmarks.push(benchmark);
             }
+        }
 
-            let benchmark = Benchmark::new(&name, exclude_lang)?;
-            benchmarks.push(benchmark);
+        let unknown = filter.unknown_tags(&benchmarks);
+        if !unknown.is_empty() {
+            let mut known: Vec<String> = benchmarks
+                .iter()
+                .flat_map(|bench| bench.config.tags.iter().map(|tag| tag.to_lowercase()))
+                .collect();
+            known.sort();
+            known.dedup();
+            println!(
+                "Warning: unknown tags {}, available tags: {}",
+                unknown.join(", "),
+                known.join(", ")
+            );
         }
+
+        benchmarks.retain(|bench| filter.matches(bench));
         Ok(benchmarks)
     }
 }
+
+/// `secs` in the largest unit that keeps it above 1, e.g. `12.3 ms`
+fn format_secs(secs: f64) -> String {
+    if secs >= 1.0 {
+        format!("{secs:.3} s")
+    } else if secs >= 1e-3 {
+        format!("{:.1} ms", secs * 1e3)
+    } else {
+        format!("{:.1} µs", secs * 1e6)
+    }
+}
+
+fn run_shell(cmd: &str) -> Result<(), Error> {
+    let status = Command::new("sh")
+        .args(["-c", cmd])
+        .status()
+        .map_err(|err| Error::external_tool(cmd, err))?;
+    if !status.success() {
+        return Err(Error::external_tool(cmd, status));
+    }
+    Ok(())
+}
+
+/// Runs `teardown_cmd` when dropped, so it also runs if hyperfine fails
+struct TeardownGuard<'a>(Option<&'a str>);
+
+impl Drop for TeardownGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(teardown) = self.0
+            && let Err(err) = run_shell(teardown)
+        {
+            println!("Warning: {err}");
+        }
+    }
+}
+
+impl fmt::Display for Benchmark {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.list_languages())
+    }
+}
+
+/// Green for speedups above 1, red below, saturating at a factor of 4
+fn heatmap_color(speedup: f64) -> String {
+    let strength = (speedup.log2().abs() / 2.0).min(1.0);
+    let fade = (255.0 * (1.0 - strength * 0.6)) as u8;
+    if speedup >= 1.0 {
+        format!("rgb({fade}, 255, {fade})")
+    } else {
+        format!("rgb(255, {fade}, {fade})")
+    }
+}
+
+/// Warns if the soft limit of open file descriptors is low, running many hyperfine
+/// instances in parallel can otherwise fail with "too many open files"
+#[cfg(unix)]
+fn warn_fd_limit() {
+    use nix::sys::resource::{Resource, getrlimit};
+    if let Ok((soft, _)) = getrlimit(Resource::RLIMIT_NOFILE)
+        && soft < 256
+    {
+        log::warn!(
+            "The open file limit is {soft}, which may not be enough for parallel runs; \
+             consider raising it with `ulimit -n 1024`"
+        );
+    }
+}
+
+/// Whether `commands` can be passed to hyperfine directly, otherwise they are run
+/// through a generated script
+/// Every command has to fit in a single argument, and all of them together with the
+/// environment in the `ARG_MAX` limit of the system
+fn fits_command_line(commands: &[String]) -> bool {
+    #[cfg(unix)]
+    let arg_max = nix::unistd::sysconf(nix::unistd::SysconfVar::ARG_MAX)
+        .ok()
+        .flatten()
+        .map_or(MAX_ARG_STRLEN, |max| max as usize);
+    #[cfg(not(unix))]
+    let arg_max = MAX_ARG_STRLEN;
+    let env_len: usize = std::env::vars_os()
+        .map(|(key, value)| key.len() + value.len() + 2)
+        .sum();
+    let total: usize = commands.iter().map(|cmd| cmd.len() + 1).sum();
+    commands.iter().all(|cmd| cmd.len() < MAX_ARG_STRLEN) && total + env_len < arg_max
+}
+
+/// SARIF artifact URI of `path`, relative to `cwd` if it lies inside of it
+fn sarif_uri(path: &Path, cwd: Option<&Path>) -> String {
+    if path.is_relative() {
+        return path.to_string_lossy().replace('\\', "/");
+    }
+    match cwd.and_then(|cwd| path.strip_prefix(cwd).ok()) {
+        Some(relative) => relative.to_string_lossy().replace('\\', "/"),
+        None => format!("file://{}", path.to_string_lossy().replace('\\', "/")),
+    }
+}
+
+/// Individual run times (in seconds) of `command`, run without a shell
+fn run_hyperfine_single(command: &str, runs: u32) -> Result<Vec<f64>, Error> {
+    create_dir_all(RAW_PATH)
+        .map_err(|_| Error::path_access(&PathBuf::from(RAW_PATH), "create hyperfine path"))?;
+    let path = PathBuf::from(RAW_PATH).join(format!("{command}_single.json"));
+    let status = Command::new("hyperfine")
+        .args(["--shell=none", "--runs", &runs.to_string(), "--export-json"])
+        .arg(&path)
+        .arg(command)
+        .status()
+        .map_err(|err| Error::hyperfine(command, err))?;
+    if !status.success() {
+        return Err(Error::external_tool("hyperfine", status));
+    }
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|err| Error::file_access(&path, "read json results", err))?;
+    let json: serde_json::Value = serde_json::from_str(&contents)
+        .map_err(|err| Error::file_access(&path, "parse json results", err))?;
+    Ok(json["results"][0]["times"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|time| time.as_f64())
+        .collect())
+}
+
+/// Warns if cpu0 uses a governor that changes the frequency during the timing runs
+/// Best-effort, nothing is reported if the governor cannot be read
+#[cfg(target_os = "linux")]
+fn warn_scaling_governor() {
+    let path = "/sys/devices/system/cpu/cpu0/cpufreq/scaling_governor";
+    let Ok(governor) = std::fs::read_to_string(path) else {
+        return;
+    };
+    let governor = governor.trim();
+    if governor == "ondemand" || governor == "powersave" {
+        log::warn!(
+            "CPU frequency scaling governor is {governor}, which makes timings less accurate; \
+             consider switching to the performance governor"
+        );
+    }
+}
+
+/// Warns if the current frequencies of the cores differ by more than 10%, which means
+/// frequency scaling was active during the run
+/// Best-effort, nothing is reported if the frequencies cannot be read
+#[cfg(target_os = "linux")]
+fn warn_frequency_spread() {
+    let Ok(cpus) = read_dir("/sys/devices/system/cpu") else {
+        return;
+    };
+    // in kHz
+    let freqs: Vec<u64> = cpus
+        .filter_map(|cpu| cpu.ok())
+        .filter(|cpu| {
+            cpu.file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix("cpu"))
+                .is_some_and(|num| num.parse::<u32>().is_ok())
+        })
+        .filter_map(|cpu| std::fs::read_to_string(cpu.path().join("cpufreq/scaling_cur_freq")).ok())
+        .filter_map(|freq| freq.trim().parse().ok())
+        .collect();
+    let (Some(min), Some(max)) = (freqs.iter().min(), freqs.iter().max()) else {
+        return;
+    };
+    if (max - min) as f64 > *max as f64 * 0.1 {
+        log::warn!(
+            "CPU frequencies ranged from {} MHz to {} MHz, frequency scaling was active \
+             during the benchmark run and may have introduced timing noise",
+            min / 1000,
+            max / 1000
+        );
+    }
+}
+
+/// Sets the scaling governor of every cpufreq policy to `governor`
+/// Returns the previous governor of each policy, so they can be restored
+#[cfg(target_os = "linux")]
+fn set_scaling_governors(governor: &str) -> Result<Vec<(PathBuf, String)>, Error> {
+    let cpufreq = Path::new("/sys/devices/system/cpu/cpufreq");
+    let mut previous = vec![];
+    for policy in read_dir(cpufreq).map_err(|err| Error::read_dir(cpufreq, err))? {
+        let path = policy
+            .map_err(|_| Error::path_access(cpufreq, "read cpufreq policy"))?
+            .path();
+        if !path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("policy"))
+        {
+            continue;
+        }
+        let path = path.join("scaling_governor");
+        let current = std::fs::read_to_string(&path)
+            .map_err(|err| Error::file_access(&path, "read scaling governor", err))
+            .and_then(|current| {
+                write(&path, governor)
+                    .map_err(|err| Error::file_access(&path, "set scaling governor", err))?;
+                Ok(current)
+            });
+        match current {
+            Ok(current) => previous.push((path, current.trim().to_owned())),
+            Err(err) => {
+                // don't leave the policies switched so far on `governor`
+                let _ = restore_scaling_governors(previous);
+                return Err(err);
+            }
+        }
+    }
+    Ok(previous)
+}
+
+/// Resets every policy returned by `set_scaling_governors` to its previous governor
+/// Keeps going if one fails, returning the first error
+#[cfg(target_os = "linux")]
+fn restore_scaling_governors(previous: Vec<(PathBuf, String)>) -> Result<(), Error> {
+    let mut result = Ok(());
+    for (path, governor) in previous {
+        if let Err(err) = write(&path, governor) {
+            let err = Error::file_access(&path, "restore scaling governor", err);
+            result = result.and(Err(err));
+        }
+    }
+    result
+}
+
+/// Line of the kernel log reporting that the OOM killer ended `pid`
+/// e.g. "Memory cgroup out of memory: Killed process 1234 (fib) total-vm:..., anon-rss:524288kB, ..."
+#[cfg(target_os = "linux")]
+fn oom_kill_line(dmesg: &str, pid: u32) -> Option<&str> {
+    let killed = format!("Killed process {pid} ");
+    dmesg.lines().rev().find(|line| line.contains(&killed))
+}
+
+/// Resident memory of the killed process, which approximates the limit it ran into
+#[cfg(target_os = "linux")]
+fn anon_rss_mb(oom_line: &str) -> Option<u64> {
+    let rss = oom_line.split("anon-rss:").nth(1)?;
+    let kb: u64 = rss.split("kB").next()?.trim().parse().ok()?;
+    Some(kb / 1024)
+}
+
+/// `oom_kill` counter of the cgroup this process runs in (cgroup v2 only)
+#[cfg(target_os = "linux")]
+fn cgroup_oom_kills() -> Option<u64> {
+    let cgroup = std::fs::read_to_string("/proc/self/cgroup").ok()?;
+    let cgroup = cgroup.lines().find_map(|line| line.strip_prefix("0::"))?;
+    let events_path = Path::new("/sys/fs/cgroup")
+        .join(cgroup.trim_start_matches('/'))
+        .join("memory.events");
+    std::fs::read_to_string(events_path)
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix("oom_kill "))?
+        .trim()
+        .parse()
+        .ok()
+}
@@ -1,6 +1,15 @@
-use std::path::PathBuf;
+use super::{
+    errors::Error,
+    tools::{available_parallelism, shell_quote},
+};
+use serde::de::{Deserializer, MapAccess, Visitor};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, Clone)]
 pub struct Config {
     pub args: Vec<String>,
     pub runs: u32,
@@ -8,6 +17,233 @@ pub struct Config {
     pub test_args: Vec<String>,
     pub expected: String,
     pub suite: String,
+    /// Additional labelled argument sets, given as `scenario.<label> = [<args>]`
+    /// When present, every language is benchmarked once per scenario instead of with `args`
+    #[serde(
+        default,
+        rename = "scenario",
+        deserialize_with = "deserialize_scenarios"
+    )]
+    pub scenarios: Vec<(String, Vec<String>)>,
+    /// Number of warmup runs before hyperfine starts timing
+    #[serde(default = "default_warmup")]
+    pub warmup: u32,
+    /// Abort on the first failing language instead of skipping it
+    #[serde(default)]
+    pub strict: bool,
+    /// Tags for selecting benchmarks, e.g. `tags = ["recursion", "gc"]`
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Pass `--show-output` to hyperfine, printing the output of every run
+    #[serde(default)]
+    pub show_output: bool,
+    /// Exported as `CFLAGS` to compilers, for toolchains that build through C
+    #[serde(default)]
+    pub c_flags: Vec<String>,
+    /// Exported as `CXXFLAGS` to compilers, for toolchains that build through C++
+    #[serde(default)]
+    pub cxx_flags: Vec<String>,
+    /// Passed to hyperfine's `--cleanup`, which runs it after the timing runs of each command
+    #[serde(default)]
+    pub cleanup_cmd: Option<String>,
+    /// Shell command run once before hyperfine, e.g. for mounting a RAM disk
+    #[serde(default)]
+    pub setup_cmd: Option<String>,
+    /// Shell command run once after hyperfine, even if it failed
+    #[serde(default)]
+    pub teardown_cmd: Option<String>,
+    /// Cores the benchmarks are pinned to (with `taskset`) during hyperfine runs,
+    /// e.g. `affinity = single_core 2` or `affinity = cores 0,2,4`
+    #[serde(default, rename = "affinity")]
+    pub affinity_strategy: AffinityStrategy,
+    /// Core hyperfine itself is pinned to
+    #[serde(default)]
+    pub hyperfine_affinity: Option<u32>,
+    /// Enables all reproducibility measures: CPU pinning, manual warmup and metadata
+    #[serde(default)]
+    pub reproducible_mode: bool,
+    /// Appended to `args` as `--seed <value>`, so benchmarks using random data always get
+    /// the same input. Only works for benchmarks that accept a `--seed` argument
+    #[serde(default)]
+    pub pinned_seed: Option<u64>,
+    /// File passed to the benchmarks as stdin
+    #[serde(default)]
+    pub stdin_file: Option<PathBuf>,
+    /// Generate a flame graph of every language after the hyperfine run
+    #[serde(default)]
+    pub flamegraph: bool,
+    /// Keep the results of every run as `<name>_<timestamp>.csv`, with `<name>_latest.csv`
+    /// linking to the newest one. `<name>.csv` is still overwritten with a copy of the newest
+    #[serde(default)]
+    pub archive: bool,
+    /// Collect `perf stat` counters of every language before the hyperfine run (Linux only)
+    #[serde(default)]
+    pub perf_stat: bool,
+    /// If not empty, benchmarks and compilers only see these environment variables,
+    /// plus essentials like `PATH` and `HOME`
+    #[serde(default)]
+    pub env_passthrough: Vec<String>,
+    /// Print the mean run times of all languages after the hyperfine run
+    #[serde(default = "default_print_summary")]
+    pub print_summary: bool,
+    /// Set `MALLOC_ARENA_MAX=1` for the benchmarks during hyperfine runs, so glibc does not
+    /// create one malloc arena per core. On by default on Linux
+    #[serde(default = "default_set_malloc_arena_max")]
+    pub set_malloc_arena_max: bool,
+    /// `build_mode = "debug"` builds MoonBit benchmarks without `--release`
+    #[serde(default)]
+    pub build_mode: BuildMode,
+}
+
+#[derive(serde::Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(try_from = "String")]
+pub enum BuildMode {
+    #[default]
+    Release,
+    Debug,
+}
+
+impl BuildMode {
+    /// Flag selecting this mode for build tools like `moon build`
+    /// ```
+    /// # use lib::config::BuildMode;
+    /// assert_eq!(BuildMode::Release.to_flag(), Some("--release"));
+    /// assert_eq!(BuildMode::Debug.to_flag(), None);
+    /// ```
+    pub fn to_flag(&self) -> Option<&'static str> {
+        match self {
+            BuildMode::Release => Some("--release"),
+            BuildMode::Debug => None,
+        }
+    }
+
+    /// Name of the directory build artifacts are placed in
+    /// ```
+    /// # use lib::config::BuildMode;
+    /// assert_eq!(BuildMode::Release.target_dir(), "release");
+    /// assert_eq!(BuildMode::Debug.target_dir(), "debug");
+    /// ```
+    pub fn target_dir(&self) -> &'static str {
+        match self {
+            BuildMode::Release => "release",
+            BuildMode::Debug => "debug",
+        }
+    }
+}
+
+impl FromStr for BuildMode {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<BuildMode, Self::Err> {
+        match s.to_lowercase().trim() {
+            "release" => Ok(BuildMode::Release),
+            "debug" => Ok(BuildMode::Debug),
+            _ => Err(Error::parse_config("build_mode", s)),
+        }
+    }
+}
+
+impl TryFrom<String> for BuildMode {
+    type Error = Error;
+    fn try_from(s: String) -> Result<BuildMode, Self::Error> {
+        s.parse()
+    }
+}
+
+/// Which cores the benchmarks may run on
+#[derive(serde::Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(try_from = "String")]
+pub enum AffinityStrategy {
+    /// Not pinned, the scheduler decides
+    #[default]
+    None,
+    /// Pinned to all cores available to this process
+    AllAvailable,
+    SingleCore(u32),
+    SpecificCores(Vec<u32>),
+}
+
+impl AffinityStrategy {
+    /// Arguments selecting these cores for `taskset`, `None` if not pinned
+    /// ```
+    /// # use lib::config::AffinityStrategy;
+    /// assert_eq!(AffinityStrategy::SingleCore(2).to_taskset_arg(), Some("-c 2".to_owned()));
+    /// assert_eq!(
+    ///     AffinityStrategy::SpecificCores(vec![0, 2, 4]).to_taskset_arg(),
+    ///     Some("-c 0,2,4".to_owned())
+    /// );
+    /// assert_eq!(AffinityStrategy::None.to_taskset_arg(), None);
+    /// ```
+    pub fn to_taskset_arg(&self) -> Option<String> {
+        let cores = match self {
+            AffinityStrategy::None => return None,
+            AffinityStrategy::AllAvailable => {
+                format!("0-{}", available_parallelism().saturating_sub(1))
+            }
+            AffinityStrategy::SingleCore(core) => core.to_string(),
+            AffinityStrategy::SpecificCores(cores) => cores
+                .iter()
+                .map(|core| core.to_string())
+                .collect::<Vec<_>>()
+                .join(","),
+        };
+        Some(format!("-c {cores}"))
+    }
+}
+
+impl fmt::Display for AffinityStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AffinityStrategy::None => write!(f, "none"),
+            AffinityStrategy::AllAvailable => write!(f, "all"),
+            AffinityStrategy::SingleCore(core) => write!(f, "single_core {core}"),
+            AffinityStrategy::SpecificCores(cores) => {
+                let cores: Vec<String> = cores.iter().map(|core| core.to_string()).collect();
+                write!(f, "cores {}", cores.join(","))
+            }
+        }
+    }
+}
+
+impl FromStr for AffinityStrategy {
+    type Err = Error;
+    /// ```
+    /// # use lib::config::AffinityStrategy;
+    /// assert_eq!("single_core 2".parse::<AffinityStrategy>().unwrap(), AffinityStrategy::SingleCore(2));
+    /// assert_eq!(
+    ///     "cores 0,2,4".parse::<AffinityStrategy>().unwrap(),
+    ///     AffinityStrategy::SpecificCores(vec![0, 2, 4])
+    /// );
+    /// assert_eq!("all".parse::<AffinityStrategy>().unwrap(), AffinityStrategy::AllAvailable);
+    /// assert!("cores".parse::<AffinityStrategy>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<AffinityStrategy, Self::Err> {
+        let err = || Error::parse_config("affinity", s);
+        let lowercase = s.to_lowercase();
+        let (kind, cores) = lowercase
+            .trim()
+            .split_once(char::is_whitespace)
+            .unwrap_or((lowercase.trim(), ""));
+        let cores: Vec<u32> = cores
+            .split(',')
+            .map(|core| core.trim())
+            .filter(|core| !core.is_empty())
+            .map(|core| core.parse().map_err(|_| err()))
+            .collect::<Result<_, _>>()?;
+        match (kind, cores.as_slice()) {
+            ("none", []) => Ok(AffinityStrategy::None),
+            ("all" | "all_available", []) => Ok(AffinityStrategy::AllAvailable),
+            ("single_core", [core]) => Ok(AffinityStrategy::SingleCore(*core)),
+            ("cores", [_, ..]) => Ok(AffinityStrategy::SpecificCores(cores)),
+            _ => Err(err()),
+        }
+    }
+}
+
+impl TryFrom<String> for AffinityStrategy {
+    type Error = Error;
+    fn try_from(s: String) -> Result<AffinityStrategy, Self::Error> {
+        s.parse()
+    }
 }
 
 impl Default for Config {
@@ -20,18 +256,393 @@ impl Default for Config {
             test_args: vec![],
             expected: "".to_owned(),
             suite: "custom".to_owned(),
+            scenarios: vec![],
+            warmup: default_warmup(),
+            strict: false,
+            tags: vec![],
+            show_output: false,
+            c_flags: vec![],
+            cxx_flags: vec![],
+            cleanup_cmd: None,
+            setup_cmd: None,
+            teardown_cmd: None,
+            affinity_strategy: AffinityStrategy::None,
+            hyperfine_affinity: None,
+            reproducible_mode: false,
+            pinned_seed: None,
+            stdin_file: None,
+            flamegraph: false,
+            archive: false,
+            perf_stat: false,
+            env_passthrough: vec![],
+            print_summary: default_print_summary(),
+            set_malloc_arena_max: default_set_malloc_arena_max(),
+            build_mode: BuildMode::Release,
         }
     }
 }
 
+fn default_warmup() -> u32 {
+    3
+}
+
+fn default_print_summary() -> bool {
+    true
+}
+
+fn default_set_malloc_arena_max() -> bool {
+    cfg!(target_os = "linux")
+}
+
 impl Config {
     pub fn from_file(path: PathBuf) -> Config {
-        let contents = std::fs::read_to_string(path).unwrap();
-        basic_toml::from_str(&contents)
+        let Ok(contents) = std::fs::read_to_string(path) else {
+            return Config::default();
+        };
+        contents
+            .parse()
             .map_err(|err| {
                 println!("{err}");
                 err
             })
             .unwrap_or_default()
     }
+
+    /// Config from `path`, with every field that has a `BENCH_<FIELD>` environment variable
+    /// overridden by that variable
+    pub fn from_file_with_env_override(path: &Path) -> Config {
+        let mut config = Config::from_file(path.to_path_buf());
+        config.apply_env_vars();
+        config
+    }
+
+    /// Default config with fields overridden by `BENCH_<FIELD>` environment variables,
+    /// in the format of `to_env_vars`
+    pub fn from_env_vars() -> Config {
+        let mut config = Config::default();
+        config.apply_env_vars();
+        config
+    }
+
+    fn apply_env_vars(&mut self) {
+        fn parsed<T: FromStr>(name: &str) -> Option<T> {
+            let value = std::env::var(name).ok()?;
+            match value.parse() {
+                Ok(value) => Some(value),
+                Err(_) => {
+                    log::warn!("Ignoring {name}, could not parse {value:?}");
+                    None
+                }
+            }
+        }
+        let var = |name: &str| std::env::var(name).ok();
+        let list = |name: &str, sep: char| {
+            var(name).map(|value| {
+                value
+                    .split(sep)
+                    .filter(|item| !item.is_empty())
+                    .map(|item| item.to_owned())
+                    .collect::<Vec<String>>()
+            })
+        };
+
+        if let Some(args) = var("BENCH_ARGS") {
+            self.args = split_args(&args);
+        }
+        if let Some(test_args) = var("BENCH_TEST_ARGS") {
+            self.test_args = split_args(&test_args);
+        }
+        self.runs = parsed("BENCH_RUNS").unwrap_or(self.runs);
+        self.warmup = parsed("BENCH_WARMUP").unwrap_or(self.warmup);
+        self.expected = var("BENCH_EXPECTED").unwrap_or(self.expected.clone());
+        self.suite = var("BENCH_SUITE").unwrap_or(self.suite.clone());
+        self.strict = parsed("BENCH_STRICT").unwrap_or(self.strict);
+        self.tags = list("BENCH_TAGS", ',').unwrap_or(self.tags.clone());
+        self.show_output = parsed("BENCH_SHOW_OUTPUT").unwrap_or(self.show_output);
+        self.c_flags = list("BENCH_C_FLAGS", ' ').unwrap_or(self.c_flags.clone());
+        self.cxx_flags = list("BENCH_CXX_FLAGS", ' ').unwrap_or(self.cxx_flags.clone());
+        self.reproducible_mode =
+            parsed("BENCH_REPRODUCIBLE_MODE").unwrap_or(self.reproducible_mode);
+        self.flamegraph = parsed("BENCH_FLAMEGRAPH").unwrap_or(self.flamegraph);
+        self.archive = parsed("BENCH_ARCHIVE").unwrap_or(self.archive);
+        self.perf_stat = parsed("BENCH_PERF_STAT").unwrap_or(self.perf_stat);
+        self.env_passthrough =
+            list("BENCH_ENV_PASSTHROUGH", ',').unwrap_or(self.env_passthrough.clone());
+        self.print_summary = parsed("BENCH_PRINT_SUMMARY").unwrap_or(self.print_summary);
+        self.affinity_strategy = parsed("BENCH_AFFINITY").unwrap_or(self.affinity_strategy.clone());
+        self.set_malloc_arena_max =
+            parsed("BENCH_SET_MALLOC_ARENA_MAX").unwrap_or(self.set_malloc_arena_max);
+        self.build_mode = parsed("BENCH_BUILD_MODE").unwrap_or(self.build_mode);
+        self.heap_size = parsed("BENCH_HEAP_SIZE").or(self.heap_size);
+        self.cleanup_cmd = var("BENCH_CLEANUP_CMD").or(self.cleanup_cmd.take());
+        self.setup_cmd = var("BENCH_SETUP_CMD").or(self.setup_cmd.take());
+        self.teardown_cmd = var("BENCH_TEARDOWN_CMD").or(self.teardown_cmd.take());
+        self.hyperfine_affinity = parsed("BENCH_HYPERFINE_AFFINITY").or(self.hyperfine_affinity);
+        self.stdin_file = parsed("BENCH_STDIN_FILE").or(self.stdin_file.take());
+        if let Some(seed) = parsed("BENCH_PINNED_SEED") {
+            self.pinned_seed = Some(seed);
+        }
+        self.apply_pinned_seed();
+    }
+
+    fn apply_pinned_seed(&mut self) {
+        if let Some(seed) = self.pinned_seed
+            && !self.args.iter().any(|arg| arg == "--seed")
+        {
+            self.args.push("--seed".to_owned());
+            self.args.push(seed.to_string());
+        }
+    }
+
+    /// The config as `BENCH_<FIELD>` environment variables for shell scripts
+    /// Argument lists are shell-quoted and space-separated, unset options are left out
+    pub fn to_env_vars(&self) -> Vec<(String, String)> {
+        let join_args = |args: &[String]| {
+            args.iter()
+                .map(|arg| shell_quote(arg))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let mut vars = vec![
+            ("BENCH_ARGS", join_args(&self.args)),
+            ("BENCH_RUNS", self.runs.to_string()),
+            ("BENCH_WARMUP", self.warmup.to_string()),
+            ("BENCH_TEST_ARGS", join_args(&self.test_args)),
+            ("BENCH_EXPECTED", self.expected.clone()),
+            ("BENCH_SUITE", self.suite.clone()),
+            ("BENCH_STRICT", self.strict.to_string()),
+            ("BENCH_TAGS", self.tags.join(",")),
+            ("BENCH_SHOW_OUTPUT", self.show_output.to_string()),
+            ("BENCH_C_FLAGS", self.c_flags.join(" ")),
+            ("BENCH_CXX_FLAGS", self.cxx_flags.join(" ")),
+            (
+                "BENCH_REPRODUCIBLE_MODE",
+                self.reproducible_mode.to_string(),
+            ),
+            ("BENCH_FLAMEGRAPH", self.flamegraph.to_string()),
+            ("BENCH_ARCHIVE", self.archive.to_string()),
+            ("BENCH_PERF_STAT", self.perf_stat.to_string()),
+            ("BENCH_ENV_PASSTHROUGH", self.env_passthrough.join(",")),
+            ("BENCH_PRINT_SUMMARY", self.print_summary.to_string()),
+            ("BENCH_AFFINITY", self.affinity_strategy.to_string()),
+            (
+                "BENCH_SET_MALLOC_ARENA_MAX",
+                self.set_malloc_arena_max.to_string(),
+            ),
+            ("BENCH_BUILD_MODE", self.build_mode.target_dir().to_owned()),
+        ];
+        let optional = [
+            (
+                "BENCH_HEAP_SIZE",
+                self.heap_size.map(|size| size.to_string()),
+            ),
+            ("BENCH_CLEANUP_CMD", self.cleanup_cmd.clone()),
+            ("BENCH_SETUP_CMD", self.setup_cmd.clone()),
+            ("BENCH_TEARDOWN_CMD", self.teardown_cmd.clone()),
+            (
+                "BENCH_HYPERFINE_AFFINITY",
+                self.hyperfine_affinity.map(|core| core.to_string()),
+            ),
+            (
+                "BENCH_PINNED_SEED",
+                self.pinned_seed.map(|seed| seed.to_string()),
+            ),
+            (
+                "BENCH_STDIN_FILE",
+                self.stdin_file
+                    .as_ref()
+                    .map(|path| path.to_string_lossy().into_owned()),
+            ),
+        ];
+        vars.extend(
+            optional
+                .into_iter()
+                .filter_map(|(name, value)| Some((name, value?))),
+        );
+        vars.into_iter()
+            .map(|(name, value)| (name.to_owned(), value))
+            .collect()
+    }
+}
+
+impl FromStr for Config {
+    type Err = basic_toml::Error;
+    /// `args` and `test_args` lines ending in `\` are joined with the following line before
+    /// parsing, other lines are left alone so toml strings keep their line-ending backslashes
+    /// ```
+    /// # use lib::config::Config;
+    /// let config: Config = "args = [\"1\", \\\n \"2\"]\nruns = 3\ntest_args = []\n\
+    ///     expected = \"\"\nsuite = \"custom\"".parse().unwrap();
+    /// assert_eq!(config.args, vec!["1", "2"]);
+    /// let config: Config = "args = --size 10 \\\n  \"my file.txt\"\nruns = 3\n\
+    ///     test_args = []\nexpected = \"\"\"a \\\n  b\"\"\"\nsuite = \"custom\"".parse().unwrap();
+    /// assert_eq!(config.args, vec!["--size", "10", "my file.txt"]);
+    /// assert_eq!(config.expected, "a b");
+    /// ```
+    /// `args`, `test_args` and scenarios may be plain command lines, `#` starts a comment
+    /// outside of quotes
+    /// ```
+    /// # use lib::config::Config;
+    /// let config: Config = r#"
+    /// args = "my file.txt" 'a#b' c\d # input file
+    /// test_args = -n 'say "hi"'
+    /// runs = 3
+    /// expected = ""
+    /// suite = "custom"
+    /// [scenario]
+    /// small = --size 10 # fast
+    /// large = ["--size", "1000"]
+    /// "#
+    /// .parse()
+    /// .unwrap();
+    /// assert_eq!(config.args, vec!["my file.txt", "a#b", "c\\d"]);
+    /// assert_eq!(config.test_args, vec!["-n", r#"say "hi""#]);
+    /// assert_eq!(
+    ///     config.scenarios,
+    ///     vec![
+    ///         ("small".to_owned(), vec!["--size".to_owned(), "10".to_owned()]),
+    ///         ("large".to_owned(), vec!["--size".to_owned(), "1000".to_owned()]),
+    ///     ]
+    /// );
+    /// ```
+    fn from_str(s: &str) -> Result<Config, Self::Err> {
+        let mut config: Config = basic_toml::from_str(&quote_arg_lines(&join_continued_lines(s)))?;
+        config.apply_pinned_seed();
+        Ok(config)
+    }
+}
+
+/// Splits a command line into arguments at whitespace, keeping single- and double-quoted
+/// parts together
+/// ```
+/// # use lib::config::split_args;
+/// assert_eq!(split_args(r#""my file.txt" other"#), vec!["my file.txt", "other"]);
+/// assert_eq!(split_args("'a b'c  d"), vec!["a bc", "d"]);
+/// ```
+pub fn split_args(s: &str) -> Vec<String> {
+    let mut args = vec![];
+    let mut current: Option<String> = None;
+    let mut quote = None;
+    for c in s.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.get_or_insert_default().push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                current.get_or_insert_default();
+            }
+            (None, c) if c.is_whitespace() => args.extend(current.take()),
+            (None, c) => current.get_or_insert_default().push(c),
+        }
+    }
+    args.extend(current);
+    args
+}
+
+// `args`, `test_args` and the entries of the `[scenario]` table may also be given as a plain
+// command line, e.g. `args = "my file.txt" other`, which is rewritten to a toml array
+// `affinity = single_core 2` is rewritten to a toml string
+// a `#` outside of quotes starts a comment in both
+fn quote_arg_lines(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len());
+    let mut in_scenarios = false;
+    for line in s.lines() {
+        if line.trim_start().starts_with('[') {
+            in_scenarios = line.trim() == "[scenario]";
+        }
+        let affinity_line = line.split_once('=').filter(|(key, value)| {
+            key.trim() == "affinity" && !value.trim_start().starts_with(['"', '\''])
+        });
+        if let Some((key, value)) = affinity_line {
+            quoted.push_str(&format!("{key}= \"{}\"\n", strip_comment(value).trim()));
+            continue;
+        }
+        let arg_line = line.split_once('=').filter(|(key, value)| {
+            (in_scenarios || matches!(key.trim(), "args" | "test_args"))
+                && !value.trim_start().starts_with('[')
+        });
+        match arg_line {
+            Some((key, value)) => {
+                let args: Vec<String> = split_args(strip_comment(value))
+                    .iter()
+                    .map(|arg| format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\"")))
+                    .collect();
+                quoted.push_str(&format!("{key}= [{}]", args.join(", ")));
+            }
+            None => quoted.push_str(line),
+        }
+        quoted.push('\n');
+    }
+    quoted
+}
+
+// cuts `s` at the first `#` that is not quoted
+fn strip_comment(s: &str) -> &str {
+    let mut quote = None;
+    for (ind, c) in s.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => return &s[..ind],
+            _ => {}
+        }
+    }
+    s
+}
+
+fn is_arg_line(line: &str) -> bool {
+    line.split_once('=')
+        .is_some_and(|(key, _)| matches!(key.trim(), "args" | "test_args"))
+}
+
+// only `args` and `test_args` lines are joined, a `\` ending a line inside a toml string
+// is part of the string
+fn join_continued_lines(s: &str) -> String {
+    let mut joined = String::with_capacity(s.len());
+    let mut continuing = false;
+    for line in s.lines() {
+        let continued = line
+            .trim_end()
+            .strip_suffix('\\')
+            .filter(|_| continuing || is_arg_line(line));
+        match continued {
+            Some(continued) => {
+                continuing = true;
+                joined.push_str(continued);
+                joined.push(' ');
+            }
+            None => {
+                continuing = false;
+                joined.push_str(line);
+                joined.push('\n');
+            }
+        }
+    }
+    joined
+}
+
+// scenarios are kept in the order they appear in the config file
+struct ScenarioVisitor;
+
+impl<'de> Visitor<'de> for ScenarioVisitor {
+    type Value = Vec<(String, Vec<String>)>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a table of scenario labels to argument lists")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut scenarios = vec![];
+        while let Some(scenario) = map.next_entry()? {
+            scenarios.push(scenario);
+        }
+        Ok(scenarios)
+    }
+}
+
+fn deserialize_scenarios<'de, D>(deserializer: D) -> Result<Vec<(String, Vec<String>)>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_map(ScenarioVisitor)
 }
@@ -2,6 +2,7 @@ use super::langs::BenchmarkLanguage;
 use std::{
     fmt,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
 #[derive(Debug)]
@@ -68,6 +69,68 @@ pub enum Error {
     WrongFormatCommand {
         command: String,
     },
+    DuplicateSourceFile {
+        benchmark: String,
+        path_a: PathBuf,
+        path_b: PathBuf,
+    },
+    DuplicateBenchmark {
+        name: String,
+        path_a: PathBuf,
+        path_b: PathBuf,
+    },
+    Gist {
+        bench: String,
+        msg: String,
+    },
+    ParseConfig {
+        field: String,
+        value: String,
+    },
+    AsanError {
+        benchmark: String,
+        lang: String,
+        report: String,
+    },
+    TsanError {
+        benchmark: String,
+        lang: String,
+        report: String,
+    },
+    UnsupportedLanguage {
+        operation: String,
+        lang: BenchmarkLanguage,
+    },
+    ExternalTool {
+        tool: String,
+        msg: String,
+    },
+    MissingTool {
+        tool: String,
+    },
+    CompileTimeLimitExceeded {
+        benchmark: String,
+        lang: BenchmarkLanguage,
+        limit_secs: f64,
+        actual_secs: f64,
+    },
+    OomKilled {
+        benchmark: String,
+        lang: String,
+        /// Memory the process used when it was killed, taken from the kernel log
+        estimated_limit_mb: Option<u64>,
+    },
+    HyperfineTooOld {
+        feature: String,
+        required_version: (u32, u32, u32),
+        found_version: (u32, u32, u32),
+    },
+    OutputLineMismatch {
+        benchmark: String,
+        lang: BenchmarkLanguage,
+        expected: usize,
+        got: usize,
+    },
 }
 
 impl Error {
@@ -171,6 +234,124 @@ impl Error {
             command: command.to_owned(),
         }
     }
+
+    pub fn duplicate_source(benchmark: &str, path_a: &Path, path_b: &Path) -> Error {
+        Error::DuplicateSourceFile {
+            benchmark: benchmark.to_owned(),
+            path_a: path_a.to_path_buf(),
+            path_b: path_b.to_path_buf(),
+        }
+    }
+
+    pub fn gist<T: fmt::Display>(bench: &str, err: T) -> Error {
+        Error::Gist {
+            bench: bench.to_owned(),
+            msg: err.to_string(),
+        }
+    }
+
+    pub fn parse_config(field: &str, value: &str) -> Error {
+        Error::ParseConfig {
+            field: field.to_owned(),
+            value: value.to_owned(),
+        }
+    }
+
+    pub fn asan(name: &str, lang: &BenchmarkLanguage, report: &str) -> Error {
+        Error::AsanError {
+            benchmark: name.to_owned(),
+            lang: lang.to_string(),
+            report: truncate_report(report),
+        }
+    }
+
+    pub fn tsan(name: &str, lang: &BenchmarkLanguage, report: &str) -> Error {
+        Error::TsanError {
+            benchmark: name.to_owned(),
+            lang: lang.to_string(),
+            report: truncate_report(report),
+        }
+    }
+
+    pub fn unsupported_lang(operation: &str, lang: &BenchmarkLanguage) -> Error {
+        Error::UnsupportedLanguage {
+            operation: operation.to_owned(),
+            lang: *lang,
+        }
+    }
+
+    pub fn external_tool<T: fmt::Display>(tool: &str, err: T) -> Error {
+        Error::ExternalTool {
+            tool: tool.to_owned(),
+            msg: err.to_string(),
+        }
+    }
+
+    pub fn missing_tool(tool: &str) -> Error {
+        Error::MissingTool {
+            tool: tool.to_owned(),
+        }
+    }
+
+    pub fn oom_killed(
+        name: &str,
+        lang: &BenchmarkLanguage,
+        estimated_limit_mb: Option<u64>,
+    ) -> Error {
+        Error::OomKilled {
+            benchmark: name.to_owned(),
+            lang: lang.to_string(),
+            estimated_limit_mb,
+        }
+    }
+
+    pub fn compile_time_exceeded(
+        name: &str,
+        lang: &BenchmarkLanguage,
+        limit: Duration,
+        actual: Duration,
+    ) -> Error {
+        Error::CompileTimeLimitExceeded {
+            benchmark: name.to_owned(),
+            lang: *lang,
+            limit_secs: limit.as_secs_f64(),
+            actual_secs: actual.as_secs_f64(),
+        }
+    }
+
+    pub fn hyperfine_too_old(
+        feature: &str,
+        required_version: (u32, u32, u32),
+        found_version: (u32, u32, u32),
+    ) -> Error {
+        Error::HyperfineTooOld {
+            feature: feature.to_owned(),
+            required_version,
+            found_version,
+        }
+    }
+
+    pub fn output_line_mismatch(
+        name: &str,
+        lang: &BenchmarkLanguage,
+        expected: usize,
+        got: usize,
+    ) -> Error {
+        Error::OutputLineMismatch {
+            benchmark: name.to_owned(),
+            lang: *lang,
+            expected,
+            got,
+        }
+    }
+
+    pub fn duplicate_bench(name: &str, path_a: &Path, path_b: &Path) -> Error {
+        Error::DuplicateBenchmark {
+            name: name.to_owned(),
+            path_a: path_a.to_path_buf(),
+            path_b: path_b.to_path_buf(),
+        }
+    }
 }
 
 impl fmt::Display for Error {
@@ -218,8 +399,162 @@ impl fmt::Display for Error {
             Error::WrongFormatCommand { command } => {
                 write!(f, "Could not parse command: {command}")
             }
+            Error::DuplicateSourceFile {
+                benchmark,
+                path_a,
+                path_b,
+            } => write!(
+                f,
+                "{path_a:?} and {path_b:?} of {benchmark} are sources for the same language, remove or rename one of them"
+            ),
+            Error::DuplicateBenchmark {
+                name,
+                path_a,
+                path_b,
+            } => write!(
+                f,
+                "Benchmark {name} exists in both {path_a:?} and {path_b:?}"
+            ),
+            Error::ParseConfig { field, value } => {
+                write!(f, "Could not parse {value} as value for {field}")
+            }
+            Error::AsanError {
+                benchmark,
+                lang,
+                report,
+            } => write!(
+                f,
+                "AddressSanitizer found errors in {benchmark} ({lang}):\n\t{report}"
+            ),
+            Error::TsanError {
+                benchmark,
+                lang,
+                report,
+            } => write!(
+                f,
+                "ThreadSanitizer found data races in {benchmark} ({lang}):\n\t{report}"
+            ),
+            Error::UnsupportedLanguage { operation, lang } => {
+                write!(
+                    f,
+                    "operation '{operation}' is not supported for language '{lang}'"
+                )
+            }
+            Error::ExternalTool { tool, msg } => write!(f, "Error running {tool}:\n\t{msg}"),
+            Error::MissingTool { tool } => write!(f, "Could not find {tool} in PATH"),
+            Error::CompileTimeLimitExceeded {
+                benchmark,
+                lang,
+                limit_secs,
+                actual_secs,
+            } => write!(
+                f,
+                "Compiling {benchmark} ({lang}) took {actual_secs:.2}s, \
+                 {:.2}s over the limit of {limit_secs:.2}s\n\t\
+                 Increase the limit or simplify the benchmark source",
+                actual_secs - limit_secs
+            ),
+            Error::OomKilled {
+                benchmark,
+                lang,
+                estimated_limit_mb,
+            } => {
+                write!(
+                    f,
+                    "{benchmark} ({lang}) ran out of memory and was killed by the kernel"
+                )?;
+                if let Some(limit) = estimated_limit_mb {
+                    write!(f, " after using about {limit} MB")?;
+                }
+                write!(
+                    f,
+                    "\n\tTry increasing the cgroup memory limit or the heap_size of the benchmark"
+                )
+            }
+            Error::Gist { bench, msg } => {
+                write!(f, "Could not upload results of {bench} to gist:\n\t{msg}")
+            }
+            Error::HyperfineTooOld {
+                feature,
+                required_version: (req_major, req_minor, req_patch),
+                found_version: (major, minor, patch),
+            } => write!(
+                f,
+                "{feature} needs hyperfine {req_major}.{req_minor}.{req_patch} or newer, \
+                 but found {major}.{minor}.{patch}\n\t\
+                 Upgrade hyperfine, see https://github.com/sharkdp/hyperfine/releases"
+            ),
+            Error::OutputLineMismatch {
+                benchmark,
+                lang,
+                expected,
+                got,
+            } => write!(
+                f,
+                "{benchmark} ({lang}) printed {got} lines, expected {expected}\n\t\
+                 Run it on the test inputs manually to inspect the output"
+            ),
         }
     }
 }
 
 impl std::error::Error for Error {}
+
+const MAX_REPORT_LEN: usize = 4096;
+
+// sanitizer reports can be very long, only keep the beginning
+fn truncate_report(report: &str) -> String {
+    if report.len() <= MAX_REPORT_LEN {
+        return report.to_owned();
+    }
+    let mut end = MAX_REPORT_LEN;
+    while !report.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}...", &report[..end])
+}
+
+/// Problems with a benchmark directory that do not prevent running it
+#[derive(Debug)]
+pub enum ValidationWarning {
+    MissingTool {
+        bench: String,
+        lang: BenchmarkLanguage,
+        tool: String,
+    },
+    MissingArgs {
+        bench: String,
+        path: PathBuf,
+    },
+    NoLanguages {
+        bench: String,
+    },
+    MissingExpected {
+        bench: String,
+    },
+}
+
+impl fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationWarning::MissingTool { bench, lang, tool } => {
+                write!(
+                    f,
+                    "{bench} has a source for {lang}, but {tool} is not in PATH"
+                )
+            }
+            ValidationWarning::MissingArgs { bench, path } => {
+                write!(f, "{bench} has no config {path:?}, using defaults")
+            }
+            ValidationWarning::NoLanguages { bench } => {
+                write!(f, "{bench} does not contain sources for any language")
+            }
+            ValidationWarning::MissingExpected { bench } => {
+                write!(
+                    f,
+                    "{bench} has no expected output, test results cannot be checked"
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use super::{benchmark::Benchmark, langs::BenchmarkLanguage};
+
+/// Selects which benchmarks are loaded from the suite
+/// Empty include lists select everything, tags are compared case-insensitively
+#[derive(Debug, Default, Clone)]
+pub struct BenchmarkFilter {
+    pub include_tags: Vec<String>,
+    pub exclude_tags: Vec<String>,
+    /// Glob patterns (`*` and `?`) for benchmark names
+    pub include_names: Vec<String>,
+    pub exclude_names: Vec<String>,
+    /// Benchmarks without an implementation for one of these languages are skipped
+    pub required_langs: Vec<BenchmarkLanguage>,
+    /// Languages that are not loaded for any benchmark
+    pub exclude_lang: Vec<BenchmarkLanguage>,
+}
+
+impl BenchmarkFilter {
+    pub fn excluding(
+        exclude_lang: &[BenchmarkLanguage],
+        exclude_bench: &[String],
+    ) -> BenchmarkFilter {
+        BenchmarkFilter {
+            exclude_names: exclude_bench.to_vec(),
+            exclude_lang: exclude_lang.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    pub fn matches_name(&self, name: &str) -> bool {
+        (self.include_names.is_empty()
+            || self.include_names.iter().any(|pat| glob_match(pat, name)))
+            && !self.exclude_names.iter().any(|pat| glob_match(pat, name))
+    }
+
+    pub fn matches(&self, bench: &Benchmark) -> bool {
+        let has_tag = |tag: &String| {
+            bench
+                .config
+                .tags
+                .iter()
+                .any(|other| other.eq_ignore_ascii_case(tag))
+        };
+        self.matches_name(&bench.name)
+            && (self.include_tags.is_empty() || self.include_tags.iter().any(has_tag))
+            && !self.exclude_tags.iter().any(has_tag)
+            && self
+                .required_langs
+                .iter()
+                .all(|lang| bench.languages.contains(lang))
+    }
+
+    /// Tags used in the filter that no benchmark in `benchmarks` has
+    pub fn unknown_tags(&self, benchmarks: &[Benchmark]) -> Vec<String> {
+        self.include_tags
+            .iter()
+            .chain(self.exclude_tags.iter())
+            .filter(|tag| {
+                !benchmarks
+                    .iter()
+                    .flat_map(|bench| bench.config.tags.iter())
+                    .any(|other| other.eq_ignore_ascii_case(tag))
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // position of the last `*` and the name index it was matched against
+    let mut backtrack = None;
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = backtrack {
+            p = star_p + 1;
+            n = star_n + 1;
+            backtrack = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
@@ -0,0 +1,76 @@
+//! Helpers for the standalone html reports
+
+const KEYWORDS: [&str; 30] = [
+    "fn", "let", "in", "if", "then", "else", "match", "with", "case", "of", "def", "fun", "val",
+    "type", "struct", "enum", "impl", "return", "while", "for", "loop", "rec", "and", "end",
+    "module", "import", "pub", "mut", "data", "effect",
+];
+
+pub fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Wraps `body` in a complete html document
+pub fn page(title: &str, style: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+         <style>\n{style}\n</style>\n</head>\n<body>\n{body}\n</body>\n</html>\n",
+        escape(title)
+    )
+}
+
+/// Escapes a line of source code and marks keywords, numbers, strings and line comments
+/// with the classes `kw`, `num`, `str` and `com`
+/// ```
+/// # use lib::html::highlight_line;
+/// assert_eq!(
+///     highlight_line("let x = 1 // one"),
+///     r#"<span class="kw">let</span> x = <span class="num">1</span> <span class="com">// one</span>"#
+/// );
+/// ```
+pub fn highlight_line(line: &str) -> String {
+    let mut out = String::new();
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        let token_len = if ["//", "--", "(*", "#"]
+            .iter()
+            .any(|start| rest.starts_with(start))
+        {
+            out.push_str(&span("com", rest));
+            break;
+        } else if c == '"' {
+            let len = rest[1..].find('"').map_or(rest.len(), |end| end + 2);
+            out.push_str(&span("str", &rest[..len]));
+            len
+        } else if c.is_ascii_digit() {
+            let len = rest
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '.' && c != '_')
+                .unwrap_or(rest.len());
+            out.push_str(&span("num", &rest[..len]));
+            len
+        } else if c.is_alphabetic() || c == '_' {
+            let len = rest
+                .find(|c: char| !c.is_alphanumeric() && c != '_' && c != '\'')
+                .unwrap_or(rest.len());
+            let word = &rest[..len];
+            if KEYWORDS.contains(&word) {
+                out.push_str(&span("kw", word));
+            } else {
+                out.push_str(&escape(word));
+            }
+            len
+        } else {
+            out.push_str(&escape(&c.to_string()));
+            c.len_utf8()
+        };
+        rest = &rest[token_len..];
+    }
+    out
+}
+
+fn span(class: &str, text: &str) -> String {
+    format!("<span class=\"{class}\">{}</span>", escape(text))
+}
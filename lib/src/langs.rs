@@ -1,11 +1,13 @@
 #![allow(unused_imports)]
-use super::{
-    errors::Error,
-    paths::{bin_path_aarch, bin_path_x86},
+use super::{errors::Error, tools::on_path};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
 };
-use std::{fmt, path::PathBuf, process::Command, str::FromStr};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum BenchmarkLanguage {
     Scc,
     Rust,
@@ -15,10 +17,22 @@ pub enum BenchmarkLanguage {
     Effekt,
     Koka,
     MoonBit,
+    Gleam,
+    Grain,
+    Fennel,
+    Unison,
+    Verse,
+    Ante,
+    /// Sources use `.beef`, as `.bf` is commonly used for Brainfuck
+    Beef,
+    Lobster,
+    Mojo,
+    /// Experimental: Carbon has no stable toolchain yet, the command line may change
+    Carbon,
 }
 
 impl BenchmarkLanguage {
-    pub fn all() -> [BenchmarkLanguage; 8] {
+    pub fn all() -> [BenchmarkLanguage; 18] {
         [
             BenchmarkLanguage::Scc,
             BenchmarkLanguage::Rust,
@@ -28,9 +42,26 @@ impl BenchmarkLanguage {
             BenchmarkLanguage::Effekt,
             BenchmarkLanguage::Koka,
             BenchmarkLanguage::MoonBit,
+            BenchmarkLanguage::Gleam,
+            BenchmarkLanguage::Grain,
+            BenchmarkLanguage::Fennel,
+            BenchmarkLanguage::Unison,
+            BenchmarkLanguage::Verse,
+            BenchmarkLanguage::Ante,
+            BenchmarkLanguage::Beef,
+            BenchmarkLanguage::Lobster,
+            BenchmarkLanguage::Mojo,
+            BenchmarkLanguage::Carbon,
         ]
     }
 
+    /// Language for the last extension component of a source file
+    /// Anything that is not a source extension (e.g. `gz` for `Fib.rs.gz`) is ignored
+    /// ```
+    /// # use lib::langs::BenchmarkLanguage;
+    /// assert_eq!(BenchmarkLanguage::from_ext("gz"), None);
+    /// assert_eq!(BenchmarkLanguage::from_ext("tar.gz"), None);
+    /// ```
     pub fn from_ext(ext: &str) -> Option<BenchmarkLanguage> {
         match ext {
             "sc" => Some(BenchmarkLanguage::Scc),
@@ -41,10 +72,32 @@ impl BenchmarkLanguage {
             "effekt" => Some(BenchmarkLanguage::Effekt),
             "kk" => Some(BenchmarkLanguage::Koka),
             "mbt" => Some(BenchmarkLanguage::MoonBit),
+            "gleam" => Some(BenchmarkLanguage::Gleam),
+            "gr" => Some(BenchmarkLanguage::Grain),
+            "fnl" => Some(BenchmarkLanguage::Fennel),
+            "u" => Some(BenchmarkLanguage::Unison),
+            "verse" => Some(BenchmarkLanguage::Verse),
+            "ante" => Some(BenchmarkLanguage::Ante),
+            "beef" => Some(BenchmarkLanguage::Beef),
+            "lobster" => Some(BenchmarkLanguage::Lobster),
+            "mojo" => Some(BenchmarkLanguage::Mojo),
+            "carbon" => Some(BenchmarkLanguage::Carbon),
             _ => None,
         }
     }
 
+    /// Language of a source file name like `Fib.rs`, `None` for files without an extension
+    /// ```
+    /// # use lib::langs::BenchmarkLanguage;
+    /// assert_eq!(BenchmarkLanguage::from_file_name("Fib.rs"), Some(BenchmarkLanguage::Rust));
+    /// assert_eq!(BenchmarkLanguage::from_file_name("Makefile"), None);
+    /// assert_eq!(BenchmarkLanguage::from_file_name(".ml"), None);
+    /// ```
+    pub fn from_file_name(file_name: &str) -> Option<BenchmarkLanguage> {
+        let ext = Path::new(file_name).extension()?.to_str()?;
+        BenchmarkLanguage::from_ext(ext)
+    }
+
     pub fn ext(&self) -> &str {
         match self {
             BenchmarkLanguage::Scc => "sc",
@@ -55,6 +108,16 @@ impl BenchmarkLanguage {
             BenchmarkLanguage::Effekt => "effekt",
             BenchmarkLanguage::Koka => "kk",
             BenchmarkLanguage::MoonBit => "mbt",
+            BenchmarkLanguage::Gleam => "gleam",
+            BenchmarkLanguage::Grain => "gr",
+            BenchmarkLanguage::Fennel => "fnl",
+            BenchmarkLanguage::Unison => "u",
+            BenchmarkLanguage::Verse => "verse",
+            BenchmarkLanguage::Ante => "ante",
+            BenchmarkLanguage::Beef => "beef",
+            BenchmarkLanguage::Lobster => "lobster",
+            BenchmarkLanguage::Mojo => "mojo",
+            BenchmarkLanguage::Carbon => "carbon",
         }
     }
 
@@ -68,6 +131,80 @@ impl BenchmarkLanguage {
             BenchmarkLanguage::Effekt => "effekt",
             BenchmarkLanguage::Koka => "koka",
             BenchmarkLanguage::MoonBit => "moonbit",
+            BenchmarkLanguage::Gleam => "gleam",
+            BenchmarkLanguage::Grain => "grain",
+            BenchmarkLanguage::Fennel => "fennel",
+            BenchmarkLanguage::Unison => "unison",
+            BenchmarkLanguage::Verse => "verse",
+            BenchmarkLanguage::Ante => "ante",
+            BenchmarkLanguage::Beef => "beef",
+            BenchmarkLanguage::Lobster => "lobster",
+            BenchmarkLanguage::Mojo => "mojo",
+            BenchmarkLanguage::Carbon => "carbon",
+        }
+    }
+
+    /// Executables needed to compile and run benchmarks in this language
+    pub fn required_tools(&self) -> &'static [&'static str] {
+        match self {
+            BenchmarkLanguage::Scc => &["scc"],
+            BenchmarkLanguage::Rust => &["rustc"],
+            BenchmarkLanguage::SmlNj => &["ml-build", "sml"],
+            BenchmarkLanguage::SmlMlton => &["mlton"],
+            BenchmarkLanguage::OCaml => &["ocamlopt"],
+            BenchmarkLanguage::Effekt => &["effekt"],
+            BenchmarkLanguage::Koka => &["koka"],
+            BenchmarkLanguage::MoonBit => &["moon"],
+            BenchmarkLanguage::Gleam => &["gleam"],
+            BenchmarkLanguage::Grain => &["grain", "wasmtime"],
+            BenchmarkLanguage::Fennel => &["fennel"],
+            BenchmarkLanguage::Unison => &["ucm"],
+            BenchmarkLanguage::Verse => &["verse-cli"],
+            BenchmarkLanguage::Ante => &["ante"],
+            BenchmarkLanguage::Beef => &["beefy"],
+            BenchmarkLanguage::Lobster => &["lobster"],
+            BenchmarkLanguage::Mojo => &["mojo"],
+            BenchmarkLanguage::Carbon => &["carbon"],
+        }
+    }
+
+    /// Interchangeable compilers of this language, the preferred one first. Empty for
+    /// languages with only one compiler, which currently is every language but OCaml
+    /// The candidates produce the same code, so the choice only affects compile times
+    pub fn compiler_candidates(&self) -> &'static [&'static str] {
+        match self {
+            // ocamlopt.opt is ocamlopt compiled to native code, it emits the same code faster
+            BenchmarkLanguage::OCaml => &["ocamlopt.opt", "ocamlopt"],
+            _ => &[],
+        }
+    }
+
+    /// The first of `compiler_candidates` that is installed
+    pub fn best_compiler(&self) -> Option<&'static str> {
+        self.compiler_candidates()
+            .iter()
+            .copied()
+            .find(|compiler| on_path(compiler))
+    }
+
+    /// Compiler `compile_cmd` runs, `best_compiler` or the first required tool
+    pub fn compiler(&self) -> &'static str {
+        self.best_compiler().unwrap_or(self.required_tools()[0])
+    }
+
+    /// Whether benchmarks need to be compiled before running, otherwise they run from source
+    pub fn is_compiled(&self) -> bool {
+        !matches!(
+            self,
+            BenchmarkLanguage::Fennel | BenchmarkLanguage::Unison | BenchmarkLanguage::Lobster
+        )
+    }
+
+    /// Extension of compiled binaries, for languages that do not produce native executables
+    pub fn binary_extension(&self) -> Option<&'static str> {
+        match self {
+            BenchmarkLanguage::Grain => Some("wasm"),
+            _ => None,
         }
     }
 
@@ -81,23 +218,27 @@ impl BenchmarkLanguage {
             "effekt" => Ok(BenchmarkLanguage::Effekt),
             "koka" => Ok(BenchmarkLanguage::Koka),
             "moonbit" => Ok(BenchmarkLanguage::MoonBit),
+            "gleam" => Ok(BenchmarkLanguage::Gleam),
+            "grain" => Ok(BenchmarkLanguage::Grain),
+            "fennel" => Ok(BenchmarkLanguage::Fennel),
+            "unison" => Ok(BenchmarkLanguage::Unison),
+            "verse" => Ok(BenchmarkLanguage::Verse),
+            "ante" => Ok(BenchmarkLanguage::Ante),
+            "beef" => Ok(BenchmarkLanguage::Beef),
+            "lobster" => Ok(BenchmarkLanguage::Lobster),
+            "mojo" => Ok(BenchmarkLanguage::Mojo),
+            "carbon" => Ok(BenchmarkLanguage::Carbon),
             _ => Err(Error::unknown_lang("read from suffix", s)),
         }
     }
 
-    pub fn compile_cmd(&self, source_file: &PathBuf, heap_size: Option<usize>) -> Command {
-        let mut source_base = source_file
-            .as_path()
-            .file_stem()
-            .expect("Could not get file name")
-            .to_owned();
-        source_base.push("_");
-        source_base.push(self.suffix());
-        #[cfg(target_arch = "x86_64")]
-        let out_path = bin_path_x86().join(source_base);
-        #[cfg(target_arch = "aarch64")]
-        let out_path = bin_path_aarch().join(source_base);
-
+    /// Command compiling `source_file` to `out_path`, see `Benchmark::compile_out_path`
+    pub fn compile_cmd(
+        &self,
+        source_file: &PathBuf,
+        out_path: &Path,
+        heap_size: Option<usize>,
+    ) -> Command {
         match self {
             BenchmarkLanguage::Scc => {
                 let mut cmd = Command::new("scc");
@@ -140,7 +281,7 @@ impl BenchmarkLanguage {
                 cmd
             }
             BenchmarkLanguage::OCaml => {
-                let mut cmd = Command::new("ocamlopt");
+                let mut cmd = Command::new(self.compiler());
                 cmd.arg(source_file);
                 cmd.arg("-o");
                 cmd.arg(out_path);
@@ -179,6 +320,75 @@ impl BenchmarkLanguage {
                 // Here we return a harmless command (true) as a placeholder.
                 Command::new("true")
             }
+            BenchmarkLanguage::Gleam => {
+                // Gleam needs a project, which is staged in Benchmark::compile
+                Command::new("true")
+            }
+            BenchmarkLanguage::Grain => {
+                let mut cmd = Command::new("grain");
+                cmd.arg("compile");
+                cmd.arg("-o");
+                cmd.arg(out_path.with_extension("wasm"));
+                cmd.arg(source_file);
+                cmd
+            }
+            BenchmarkLanguage::Fennel => {
+                // Fennel is run from source, see Benchmark::run_cmd
+                Command::new("true")
+            }
+            BenchmarkLanguage::Unison => {
+                // Unison is run from source, see Benchmark::run_cmd
+                Command::new("true")
+            }
+            BenchmarkLanguage::Verse => {
+                // Verse is embedded in Unreal Engine, only the standalone compiler is supported
+                let mut cmd = Command::new("verse-cli");
+                cmd.arg("build");
+                cmd.arg(source_file);
+                cmd.arg("-o");
+                cmd.arg(out_path);
+                cmd
+            }
+            BenchmarkLanguage::Ante => {
+                let mut cmd = Command::new("ante");
+                cmd.arg(source_file);
+                cmd.arg("-o");
+                cmd.arg(out_path);
+                cmd
+            }
+            BenchmarkLanguage::Beef => {
+                let mut cmd = Command::new("beefy");
+                cmd.arg(source_file);
+                cmd.arg("-o");
+                cmd.arg(out_path);
+                cmd
+            }
+            BenchmarkLanguage::Lobster => {
+                // Lobster is run from source, see Benchmark::run_cmd
+                Command::new("true")
+            }
+            BenchmarkLanguage::Mojo => {
+                // the SDK is often installed outside of PATH, in which case MODULAR_HOME
+                // points to its installation
+                let mojo = std::env::var_os("MODULAR_HOME")
+                    .map(|home| PathBuf::from(home).join("pkg/packages.modular.com_mojo/bin/mojo"))
+                    .filter(|mojo| mojo.is_file())
+                    .unwrap_or(PathBuf::from("mojo"));
+                let mut cmd = Command::new(mojo);
+                cmd.arg("build");
+                cmd.arg("-o");
+                cmd.arg(out_path);
+                cmd.arg(source_file);
+                cmd
+            }
+            BenchmarkLanguage::Carbon => {
+                let mut cmd = Command::new("carbon");
+                cmd.arg("compile");
+                cmd.arg("-o");
+                cmd.arg(out_path);
+                cmd.arg(source_file);
+                cmd
+            }
         }
     }
 }
@@ -194,6 +404,16 @@ impl fmt::Display for BenchmarkLanguage {
             BenchmarkLanguage::Effekt => f.write_str("Effekt"),
             BenchmarkLanguage::Koka => f.write_str("Koka"),
             BenchmarkLanguage::MoonBit => f.write_str("MoonBit"),
+            BenchmarkLanguage::Gleam => f.write_str("Gleam"),
+            BenchmarkLanguage::Grain => f.write_str("Grain"),
+            BenchmarkLanguage::Fennel => f.write_str("Fennel"),
+            BenchmarkLanguage::Unison => f.write_str("Unison"),
+            BenchmarkLanguage::Verse => f.write_str("Verse"),
+            BenchmarkLanguage::Ante => f.write_str("Ante"),
+            BenchmarkLanguage::Beef => f.write_str("Beef"),
+            BenchmarkLanguage::Lobster => f.write_str("Lobster"),
+            BenchmarkLanguage::Mojo => f.write_str("Mojo"),
+            BenchmarkLanguage::Carbon => f.write_str("Carbon"),
         }
     }
 }
@@ -210,6 +430,16 @@ impl FromStr for BenchmarkLanguage {
             "effekt" => Ok(BenchmarkLanguage::Effekt),
             "koka" => Ok(BenchmarkLanguage::Koka),
             "moonbit" => Ok(BenchmarkLanguage::MoonBit),
+            "gleam" => Ok(BenchmarkLanguage::Gleam),
+            "grain" => Ok(BenchmarkLanguage::Grain),
+            "fennel" => Ok(BenchmarkLanguage::Fennel),
+            "unison" => Ok(BenchmarkLanguage::Unison),
+            "verse" => Ok(BenchmarkLanguage::Verse),
+            "ante" => Ok(BenchmarkLanguage::Ante),
+            "beef" => Ok(BenchmarkLanguage::Beef),
+            "lobster" => Ok(BenchmarkLanguage::Lobster),
+            "mojo" => Ok(BenchmarkLanguage::Mojo),
+            "carbon" => Ok(BenchmarkLanguage::Carbon),
             _ => Err(Error::unknown_lang("Parse Language Name", s)),
         }
     }
@@ -1,7 +1,14 @@
 pub mod benchmark;
 pub mod config;
 pub mod errors;
+pub mod filter;
+pub mod html;
 pub mod langs;
 pub mod paths;
+pub mod report;
+pub mod results;
+pub mod stats;
+pub mod svg;
+pub mod tools;
 
 pub mod test_utils;
@@ -1,4 +1,4 @@
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 
 pub const SUITE_PATH: &str = "suite";
 
@@ -9,18 +9,44 @@ pub const BIN_AARCH: &str = "aarch_64";
 pub const RAW_PATH: &str = "results/raw";
 pub const PLOTS_PATH: &str = "results/plots";
 
-pub fn bin_path_x86() -> PathBuf {
-    let path = Path::new(BIN_PATH).join(BIN_X86);
-    if !path.exists() {
-        std::fs::create_dir_all(&path).expect("Could not create out dir");
+/// Output directories of a benchmark, defaults to the constants above
+#[derive(Debug, Clone)]
+pub struct Paths {
+    /// Root of the compiled binaries, which are placed in a directory per architecture
+    pub bin: PathBuf,
+}
+
+impl Default for Paths {
+    fn default() -> Paths {
+        Paths {
+            bin: PathBuf::from(BIN_PATH),
+        }
     }
-    path
 }
 
-pub fn bin_path_aarch() -> PathBuf {
-    let path = Path::new(BIN_PATH).join(BIN_AARCH);
+impl Paths {
+    pub fn bin_path_x86(&self) -> PathBuf {
+        create_out_dir(self.bin.join(BIN_X86))
+    }
+
+    pub fn bin_path_aarch(&self) -> PathBuf {
+        create_out_dir(self.bin.join(BIN_AARCH))
+    }
+}
+
+fn create_out_dir(path: PathBuf) -> PathBuf {
     if !path.exists() {
         std::fs::create_dir_all(&path).expect("Could not create out dir");
     }
     path
 }
+
+#[deprecated(note = "use `Paths::bin_path_x86` instead")]
+pub fn bin_path_x86() -> PathBuf {
+    Paths::default().bin_path_x86()
+}
+
+#[deprecated(note = "use `Paths::bin_path_aarch` instead")]
+pub fn bin_path_aarch() -> PathBuf {
+    Paths::default().bin_path_aarch()
+}
@@ -0,0 +1,60 @@
+use super::langs::BenchmarkLanguage;
+use std::time::Duration;
+
+/// A warning printed by a compiler while building a benchmark
+#[derive(Debug, Clone)]
+pub struct CompileWarning {
+    pub lang: BenchmarkLanguage,
+    pub message: String,
+    /// Source file the warning refers to, if the compiler reported one
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+}
+
+/// Diagnostics of a successful compilation
+#[derive(Debug, Clone)]
+pub struct CompileOutput {
+    pub lang: BenchmarkLanguage,
+    /// Lines of the compiler's output mentioning a warning
+    pub warnings: Vec<String>,
+    pub duration: Duration,
+}
+
+impl CompileOutput {
+    /// Collects the lines of `stdout` and `stderr` that contain "warning" but not "error"
+    /// ```
+    /// # use lib::{langs::BenchmarkLanguage, report::CompileOutput};
+    /// # use std::time::Duration;
+    /// let out = CompileOutput::from_output(
+    ///     BenchmarkLanguage::Rust,
+    ///     b"Compiling fib\n",
+    ///     b"Warning: unused variable `n`\nerror: warnings treated as errors\n",
+    ///     Duration::from_millis(300),
+    /// );
+    /// assert_eq!(out.warnings, ["Warning: unused variable `n`"]);
+    /// ```
+    pub fn from_output(
+        lang: BenchmarkLanguage,
+        stdout: &[u8],
+        stderr: &[u8],
+        duration: Duration,
+    ) -> CompileOutput {
+        let stdout = String::from_utf8_lossy(stdout);
+        let stderr = String::from_utf8_lossy(stderr);
+        let warnings = stdout
+            .lines()
+            .chain(stderr.lines())
+            .filter(|line| {
+                let line = line.to_lowercase();
+                line.contains("warning") && !line.contains("error")
+            })
+            .map(|line| line.trim_end().to_owned())
+            .collect();
+        CompileOutput {
+            lang,
+            warnings,
+            duration,
+        }
+    }
+}
@@ -0,0 +1,366 @@
+use super::{
+    errors::Error,
+    langs::BenchmarkLanguage,
+    stats::{mean, variance},
+    tools::ProcessorInfo,
+};
+use std::{collections::HashMap, fmt, path::Path, process::ExitStatus, str, time::Duration};
+
+/// Information about a hyperfine run, stored next to the result csv
+#[derive(Debug, Default, serde::Serialize)]
+pub struct Metadata {
+    pub benchmark: String,
+    pub hyperfine_version: Option<String>,
+    pub reproducible_mode: bool,
+    /// Wall time of the whole run, including test runs and warmup
+    pub run_elapsed_secs: f64,
+    pub processor: ProcessorInfo,
+}
+
+/// Outcome of Welch's t-test comparing the run times of two languages
+#[derive(Debug, Clone)]
+pub struct WelchResult {
+    pub lang_a: String,
+    pub lang_b: String,
+    /// Negative if `lang_a` has the lower mean run time
+    pub t_statistic: f64,
+    pub p_value: f64,
+    /// Significance level the p-value is compared against
+    pub alpha: f64,
+    pub significant: bool,
+}
+
+impl fmt::Display for WelchResult {
+    /// ```
+    /// # use lib::results::WelchResult;
+    /// let res = WelchResult {
+    ///     lang_a: "Rust".to_owned(),
+    ///     lang_b: "OCaml".to_owned(),
+    ///     t_statistic: -4.2,
+    ///     p_value: 0.003,
+    ///     alpha: 0.05,
+    ///     significant: true,
+    /// };
+    /// assert_eq!(res.to_string(), "Rust is significantly faster than OCaml (p=0.003)");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (faster, slower) = if self.t_statistic < 0.0 {
+            (&self.lang_a, &self.lang_b)
+        } else {
+            (&self.lang_b, &self.lang_a)
+        };
+        if self.significant {
+            write!(
+                f,
+                "{faster} is significantly faster than {slower} (p={:.3})",
+                self.p_value
+            )
+        } else {
+            write!(
+                f,
+                "No significant difference between {} and {} (p={:.3}, alpha={})",
+                self.lang_a, self.lang_b, self.p_value, self.alpha
+            )
+        }
+    }
+}
+
+/// Predicted wall time of a benchmark run, see `Benchmark::estimate_total_run_time`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunTimeEstimate(pub Duration);
+
+impl fmt::Display for RunTimeEstimate {
+    /// ```
+    /// # use lib::results::RunTimeEstimate;
+    /// # use std::time::Duration;
+    /// assert_eq!(RunTimeEstimate(Duration::from_secs(123)).to_string(), "2m 03s");
+    /// assert_eq!(RunTimeEstimate(Duration::from_secs(3723)).to_string(), "1h 02m 03s");
+    /// assert_eq!(RunTimeEstimate(Duration::from_millis(4500)).to_string(), "4s");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let secs = self.0.as_secs();
+        let (hours, mins, secs) = (secs / 3600, secs / 60 % 60, secs % 60);
+        if hours > 0 {
+            write!(f, "{hours}h {mins:02}m {secs:02}s")
+        } else if mins > 0 {
+            write!(f, "{mins}m {secs:02}s")
+        } else {
+            write!(f, "{secs}s")
+        }
+    }
+}
+
+/// Change of the mean run time of a language between two runs
+#[derive(Debug, Clone)]
+pub struct ResultDiff {
+    pub lang: BenchmarkLanguage,
+    pub baseline_mean: f64,
+    pub new_mean: f64,
+    /// Positive if the new run is slower
+    pub change_pct: f64,
+}
+
+/// Run time variation of a command that always does the same work, measures how noisy
+/// timings on this machine are
+#[derive(Debug, Clone)]
+pub struct TimingNoiseResult {
+    pub mean_secs: f64,
+    pub stddev_secs: f64,
+    /// Coefficient of variation (standard deviation / mean) of the run times
+    pub cv: f64,
+    /// Whether `cv` is below 5%
+    pub acceptable: bool,
+    /// How to reduce the noise, empty if the timings are acceptable
+    pub recommendation: String,
+}
+
+impl TimingNoiseResult {
+    /// ```
+    /// # use lib::results::TimingNoiseResult;
+    /// let res = TimingNoiseResult::from_times(&[1.0, 1.01, 0.99]);
+    /// assert!(res.acceptable && res.recommendation.is_empty());
+    /// let res = TimingNoiseResult::from_times(&[1.0, 2.0, 0.5]);
+    /// assert!(!res.acceptable);
+    /// assert!(res.recommendation.contains("frequency scaling"));
+    /// ```
+    pub fn from_times(times: &[f64]) -> TimingNoiseResult {
+        let mean_secs = mean(times);
+        let stddev_secs = variance(times).sqrt();
+        let cv = stddev_secs / mean_secs;
+        let acceptable = cv < 0.05;
+        let recommendation = if acceptable {
+            String::new()
+        } else {
+            format!(
+                "Timings vary by {:.1}%: disable CPU frequency scaling (use the performance \
+                 governor), close background processes and pin the benchmarks to an idle core \
+                 with `affinity`",
+                cv * 100.0
+            )
+        };
+        TimingNoiseResult {
+            mean_secs,
+            stddev_secs,
+            cv,
+            acceptable,
+            recommendation,
+        }
+    }
+}
+
+/// Result of a single (non-hyperfine) benchmark execution
+#[derive(Debug)]
+pub struct RunOutcome {
+    pub lang: BenchmarkLanguage,
+    /// Label of the scenario that was run, `None` when running with plain `args`
+    pub scenario: Option<String>,
+    /// `None` if the process could not be started at all
+    pub status: Option<ExitStatus>,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+    pub duration: Duration,
+}
+
+impl RunOutcome {
+    pub fn success(&self) -> bool {
+        self.status.is_some_and(|status| status.success())
+    }
+
+    pub fn stdout_str(&self) -> &str {
+        str::from_utf8(&self.stdout).unwrap_or("")
+    }
+
+    pub fn stderr_str(&self) -> &str {
+        str::from_utf8(&self.stderr).unwrap_or("")
+    }
+}
+
+/// Properties of a compiled benchmark binary, as reported by `file` and `ldd`
+#[derive(Debug, Clone)]
+pub struct BinaryInfo {
+    pub format: String,
+    pub arch: String,
+    pub stripped: bool,
+    pub dynamic_libs: Vec<String>,
+    pub size_bytes: u64,
+}
+
+impl BinaryInfo {
+    /// Parses the output of `file --brief <bin>`
+    /// e.g. "ELF 64-bit LSB pie executable, x86-64, version 1 (SYSV), ..., not stripped"
+    /// `dynamic_libs` and `size_bytes` are not part of this output and left empty
+    pub fn from_file_output(stdout: &str) -> Result<BinaryInfo, Error> {
+        let description = stdout.trim();
+        let mut fields = description.split(", ");
+        let format = fields.next().unwrap_or("").to_owned();
+        if format.is_empty() {
+            return Err(Error::external_tool("file", "empty output"));
+        }
+        let arch = if format.starts_with("Mach-O") {
+            format.rsplit(' ').next().unwrap_or("").to_owned()
+        } else {
+            fields.next().unwrap_or("").to_owned()
+        };
+        let stripped = description
+            .split(", ")
+            .any(|field| field.trim() == "stripped");
+
+        Ok(BinaryInfo {
+            format,
+            arch,
+            stripped,
+            dynamic_libs: vec![],
+            size_bytes: 0,
+        })
+    }
+
+    /// Parses the library names from the output of `ldd <bin>`
+    /// e.g. "\tlibc.so.6 => /lib/x86_64-linux-gnu/libc.so.6 (0x00007f...)"
+    pub fn libs_from_ldd_output(stdout: &str) -> Vec<String> {
+        stdout
+            .lines()
+            .filter_map(|line| line.split_whitespace().next())
+            .filter(|lib| lib.contains(".so") && !lib.starts_with("linux-vdso"))
+            .map(|lib| lib.to_owned())
+            .collect()
+    }
+}
+
+/// Instruction count of a single language measured with callgrind
+#[derive(Debug, Clone)]
+pub struct InstructionCountResult {
+    pub lang: BenchmarkLanguage,
+    pub instructions: u64,
+    /// Last-level cache misses, only available when callgrind ran with `--cache-sim=yes`
+    pub cache_misses: Option<u64>,
+}
+
+impl InstructionCountResult {
+    /// Parses a callgrind output file named `<benchmark>_<language suffix>.out`
+    pub fn from_callgrind_output(path: &Path) -> Result<InstructionCountResult, Error> {
+        let lang = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.rsplit_once('_'))
+            .ok_or(Error::path_access(
+                path,
+                "language suffix of callgrind output",
+            ))?
+            .1;
+        let lang = BenchmarkLanguage::from_suffix(lang)?;
+
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| Error::file_access(path, "read callgrind output", err))?;
+        let events: Vec<&str> = contents
+            .lines()
+            .find_map(|line| line.strip_prefix("events:"))
+            .map(|events| events.split_whitespace().collect())
+            .unwrap_or_default();
+        let totals: Vec<u64> = contents
+            .lines()
+            .find_map(|line| {
+                line.strip_prefix("summary:")
+                    .or_else(|| line.strip_prefix("totals:"))
+            })
+            .map(|totals| {
+                totals
+                    .split_whitespace()
+                    .filter_map(|total| total.parse().ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let event_total = |event: &str| {
+            events
+                .iter()
+                .position(|name| *name == event)
+                .and_then(|ind| totals.get(ind).copied())
+        };
+
+        let instructions =
+            event_total("Ir")
+                .or(totals.first().copied())
+                .ok_or(Error::external_tool(
+                    "valgrind",
+                    "callgrind output does not contain an instruction count",
+                ))?;
+        let cache_misses = ["ILmr", "DLmr", "DLmw"]
+            .iter()
+            .map(|event| event_total(event))
+            .sum();
+        Ok(InstructionCountResult {
+            lang,
+            instructions,
+            cache_misses,
+        })
+    }
+}
+
+/// Syscall summary of a single run, as printed by `strace -c`
+#[derive(Debug, Clone)]
+pub struct StraceResult {
+    pub benchmark: String,
+    pub lang: BenchmarkLanguage,
+    /// Number of calls of each syscall
+    pub syscall_counts: HashMap<String, u64>,
+    /// Time spent in syscalls, not the run time of the benchmark
+    pub total_time_secs: f64,
+}
+
+impl StraceResult {
+    /// Parses the summary table of `strace -c`, other lines (like the benchmark's own
+    /// stderr) are skipped
+    /// ```
+    /// # use lib::{langs::BenchmarkLanguage, results::StraceResult};
+    /// let summary = "\
+    /// % time     seconds  usecs/call     calls    errors syscall
+    /// ------ ----------- ----------- --------- --------- ----------------
+    ///  60.00    0.000030          15         2           write
+    ///  40.00    0.000020           4         5         1 openat
+    /// ------ ----------- ----------- --------- --------- ----------------
+    /// 100.00    0.000050           7         7         1 total
+    /// ";
+    /// let res = StraceResult::from_strace_summary("Fib", BenchmarkLanguage::Rust, summary);
+    /// assert_eq!(res.top_syscalls(1), [("openat", 5)]);
+    /// assert_eq!(res.syscall_counts["write"], 2);
+    /// assert_eq!(res.total_time_secs, 0.00005);
+    /// ```
+    pub fn from_strace_summary(
+        benchmark: &str,
+        lang: BenchmarkLanguage,
+        stderr: &str,
+    ) -> StraceResult {
+        let mut syscall_counts = HashMap::new();
+        let mut total_time_secs = 0.0;
+        for line in stderr.lines() {
+            // % time, seconds, usecs/call, calls, [errors,] syscall
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 5 || fields[0].parse::<f64>().is_err() {
+                continue;
+            }
+            let name = fields[fields.len() - 1];
+            if name == "total" {
+                total_time_secs = fields[1].parse().unwrap_or(0.0);
+            } else if let Ok(calls) = fields[3].parse::<u64>() {
+                syscall_counts.insert(name.to_owned(), calls);
+            }
+        }
+        StraceResult {
+            benchmark: benchmark.to_owned(),
+            lang,
+            syscall_counts,
+            total_time_secs,
+        }
+    }
+
+    /// The `n` most frequently called syscalls, most calls first
+    pub fn top_syscalls(&self, n: usize) -> Vec<(&str, u64)> {
+        let mut counts: Vec<(&str, u64)> = self
+            .syscall_counts
+            .iter()
+            .map(|(name, count)| (name.as_str(), *count))
+            .collect();
+        counts.sort_by(|(name_a, a), (name_b, b)| b.cmp(a).then(name_a.cmp(name_b)));
+        counts.truncate(n);
+        counts
+    }
+}
@@ -0,0 +1,161 @@
+use std::f64::consts::PI;
+
+pub fn mean(samples: &[f64]) -> f64 {
+    samples.iter().sum::<f64>() / samples.len() as f64
+}
+
+/// Geometric mean, for averaging ratios like run times of different benchmarks
+/// ```
+/// # use lib::stats::geometric_mean;
+/// assert!((geometric_mean(&[1.0, 4.0, 16.0]) - 4.0).abs() < 1e-9);
+/// ```
+pub fn geometric_mean(samples: &[f64]) -> f64 {
+    (samples.iter().map(|x| x.ln()).sum::<f64>() / samples.len() as f64).exp()
+}
+
+/// Sample variance (with Bessel's correction)
+pub fn variance(samples: &[f64]) -> f64 {
+    let mean = mean(samples);
+    samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (samples.len() as f64 - 1.0)
+}
+
+/// Welch's t-test for two samples with possibly unequal variances
+/// Returns the t statistic and the two-sided p-value
+/// ```
+/// # use lib::stats::welch_t_test;
+/// let (t, p) = welch_t_test(&[1.0, 1.1, 0.9, 1.0], &[2.0, 2.1, 1.9, 2.0]);
+/// assert!(t < 0.0);
+/// assert!(p < 0.001);
+/// let (_, p) = welch_t_test(&[1.0, 1.2, 0.8], &[1.1, 0.9, 1.0]);
+/// assert!(p > 0.5);
+/// ```
+pub fn welch_t_test(a: &[f64], b: &[f64]) -> (f64, f64) {
+    let (n_a, n_b) = (a.len() as f64, b.len() as f64);
+    let (se_a, se_b) = (variance(a) / n_a, variance(b) / n_b);
+    let t = (mean(a) - mean(b)) / (se_a + se_b).sqrt();
+    // Welch–Satterthwaite approximation of the degrees of freedom
+    let df = (se_a + se_b).powi(2) / (se_a.powi(2) / (n_a - 1.0) + se_b.powi(2) / (n_b - 1.0));
+    (t, student_t_two_sided(t, df))
+}
+
+/// Percentile bootstrap confidence interval for the mean of `samples`
+/// Resampling is seeded, so the same samples always give the same interval
+/// ```
+/// # use lib::stats::bootstrap_mean_ci;
+/// let samples = [1.0, 1.2, 0.9, 1.1, 1.0, 0.95, 1.05];
+/// let (low, high) = bootstrap_mean_ci(&samples, 0.95, 1000);
+/// assert!(low < 1.03 && 1.03 < high);
+/// assert!(0.9 <= low && high <= 1.2);
+/// ```
+pub fn bootstrap_mean_ci(samples: &[f64], confidence: f64, resamples: usize) -> (f64, f64) {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+    let mut means: Vec<f64> = (0..resamples)
+        .map(|_| {
+            (0..samples.len())
+                .map(|_| samples[rng.next() as usize % samples.len()])
+                .sum::<f64>()
+                / samples.len() as f64
+        })
+        .collect();
+    means.sort_by(f64::total_cmp);
+    let tail = (1.0 - confidence) / 2.0;
+    let at = |quantile: f64| means[(quantile * (resamples - 1) as f64).round() as usize];
+    (at(tail), at(1.0 - tail))
+}
+
+// small deterministic generator for resampling, statistical quality is sufficient here
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+/// P(|T| >= |t|) for a Student t-distribution with `df` degrees of freedom
+fn student_t_two_sided(t: f64, df: f64) -> f64 {
+    if !t.is_finite() {
+        return if t.is_nan() { 1.0 } else { 0.0 };
+    }
+    incomplete_beta(df / 2.0, 0.5, df / (df + t * t))
+}
+
+/// Regularized incomplete beta function I_x(a, b)
+fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    // the continued fraction converges quickly only below this point
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_continued_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+// modified Lentz's method
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut result = d;
+    for m in 1..300 {
+        let m = m as f64;
+        for numerator in [
+            m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m)),
+            -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0)),
+        ] {
+            d = 1.0 + numerator * d;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = 1.0 + numerator / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            result *= d * c;
+        }
+        if (d * c - 1.0).abs() < 1e-12 {
+            break;
+        }
+    }
+    result
+}
+
+// Lanczos approximation
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        return (PI / (PI * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let sum = COEFFS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFS[0], |sum, (i, c)| sum + c / (x + i as f64 + 1.0));
+    0.5 * (2.0 * PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
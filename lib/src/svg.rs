@@ -0,0 +1,200 @@
+//! Minimal string-based SVG charts, for plots that do not need plotters
+
+use std::fmt::Write;
+
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 500.0;
+const MARGIN: f64 = 60.0;
+const PALETTE: [&str; 8] = [
+    "#1b4253", "#8f1e1c", "#f29100", "#42244a", "#8f8f8f", "#25b1e4", "#aa337f", "#2e7d32",
+];
+
+pub fn color(ind: usize) -> &'static str {
+    PALETTE[ind % PALETTE.len()]
+}
+
+/// Scatter plot of each series' values (y) against their index (x)
+pub fn scatter_plot(title: &str, y_label: &str, series: &[(String, Vec<f64>)]) -> String {
+    let x_max = series
+        .iter()
+        .map(|(_, values)| values.len())
+        .max()
+        .unwrap_or(1)
+        .max(2) as f64
+        - 1.0;
+    let y_max = max_value(series.iter().flat_map(|(_, values)| values.iter()));
+
+    let mut svg = header(title);
+    axes(&mut svg, "run", y_label, y_max);
+    for (ind, (name, values)) in series.iter().enumerate() {
+        for (run, value) in values.iter().enumerate() {
+            let _ = write!(
+                svg,
+                r#"<circle cx="{:.1}" cy="{:.1}" r="3" fill="{}"><title>{name} #{run}: {value}</title></circle>"#,
+                x_pos(run as f64, x_max),
+                y_pos(*value, y_max),
+                color(ind)
+            );
+        }
+        legend(&mut svg, ind, name);
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Box plot per series, with whiskers at the minimum and maximum
+pub fn box_plot(title: &str, y_label: &str, series: &[(String, Vec<f64>)]) -> String {
+    let y_max = max_value(series.iter().flat_map(|(_, values)| values.iter()));
+    let slot = (WIDTH - 2.0 * MARGIN) / series.len().max(1) as f64;
+
+    let mut svg = header(title);
+    axes(&mut svg, "", y_label, y_max);
+    for (ind, (name, values)) in series.iter().enumerate() {
+        if values.is_empty() {
+            continue;
+        }
+        let mut sorted = values.clone();
+        sorted.sort_by(f64::total_cmp);
+        let [min, q1, median, q3, max] =
+            [0.0, 0.25, 0.5, 0.75, 1.0].map(|q| y_pos(quantile(&sorted, q), y_max));
+        let center = MARGIN + slot * (ind as f64 + 0.5);
+        let half = slot / 4.0;
+        let color = color(ind);
+        let _ = write!(
+            svg,
+            concat!(
+                r#"<line x1="{c:.1}" y1="{min:.1}" x2="{c:.1}" y2="{q1:.1}" stroke="{color}"/>"#,
+                r#"<line x1="{c:.1}" y1="{q3:.1}" x2="{c:.1}" y2="{max:.1}" stroke="{color}"/>"#,
+                r#"<line x1="{l:.1}" y1="{min:.1}" x2="{r:.1}" y2="{min:.1}" stroke="{color}"/>"#,
+                r#"<line x1="{l:.1}" y1="{max:.1}" x2="{r:.1}" y2="{max:.1}" stroke="{color}"/>"#,
+                r#"<rect x="{bl:.1}" y="{q3:.1}" width="{bw:.1}" height="{bh:.1}" fill="{color}" fill-opacity="0.3" stroke="{color}"/>"#,
+                r#"<line x1="{bl:.1}" y1="{median:.1}" x2="{br:.1}" y2="{median:.1}" stroke="{color}" stroke-width="2"/>"#,
+                r#"<text x="{c:.1}" y="{label_y:.1}" text-anchor="middle" font-size="12">{name}</text>"#,
+            ),
+            c = center,
+            l = center - half / 2.0,
+            r = center + half / 2.0,
+            bl = center - half,
+            br = center + half,
+            bw = 2.0 * half,
+            bh = q1 - q3,
+            label_y = HEIGHT - MARGIN + 20.0,
+            min = min,
+            q1 = q1,
+            median = median,
+            q3 = q3,
+            max = max,
+            color = color,
+            name = name,
+        );
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Linear interpolation between the closest ranks of sorted `values`
+/// Two-part badge in the style of shields.io, e.g. `timing | -12%`
+pub fn badge(label: &str, message: &str, color: &str) -> String {
+    // rough width of an 11px sans-serif character
+    let text_width = |text: &str| text.chars().count() as f64 * 7.0 + 10.0;
+    let (label_w, message_w) = (text_width(label), text_width(message));
+    format!(
+        concat!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="20" font-family="sans-serif" font-size="11">"#,
+            r##"<rect width="{label_w}" height="20" fill="#555"/>"##,
+            r#"<rect x="{label_w}" width="{message_w}" height="20" fill="{color}"/>"#,
+            r#"<text x="{label_x}" y="14" fill="white" text-anchor="middle">{label}</text>"#,
+            r#"<text x="{message_x}" y="14" fill="white" text-anchor="middle">{message}</text>"#,
+            "</svg>\n",
+        ),
+        w = label_w + message_w,
+        label_w = label_w,
+        message_w = message_w,
+        color = color,
+        label_x = label_w / 2.0,
+        message_x = label_w + message_w / 2.0,
+        label = label,
+        message = message,
+    )
+}
+
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let (low, high) = (pos.floor() as usize, pos.ceil() as usize);
+    sorted[low] + (sorted[high] - sorted[low]) * (pos - low as f64)
+}
+
+fn max_value<'a>(values: impl Iterator<Item = &'a f64>) -> f64 {
+    let max = values.fold(0.0, |max: f64, value| max.max(*value));
+    if max > 0.0 { max * 1.1 } else { 1.0 }
+}
+
+fn x_pos(x: f64, x_max: f64) -> f64 {
+    MARGIN + x / x_max * (WIDTH - 2.0 * MARGIN)
+}
+
+fn y_pos(y: f64, y_max: f64) -> f64 {
+    HEIGHT - MARGIN - y / y_max * (HEIGHT - 2.0 * MARGIN)
+}
+
+fn header(title: &str) -> String {
+    format!(
+        concat!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif">"#,
+            r#"<rect width="100%" height="100%" fill="white"/>"#,
+            r#"<text x="{x}" y="30" text-anchor="middle" font-size="18">{title}</text>"#,
+        ),
+        w = WIDTH,
+        h = HEIGHT,
+        x = WIDTH / 2.0,
+        title = title,
+    )
+}
+
+fn axes(svg: &mut String, x_label: &str, y_label: &str, y_max: f64) {
+    let (left, right, top, bottom) = (MARGIN, WIDTH - MARGIN, MARGIN, HEIGHT - MARGIN);
+    let _ = write!(
+        svg,
+        concat!(
+            r#"<line x1="{left}" y1="{bottom}" x2="{right}" y2="{bottom}" stroke="black"/>"#,
+            r#"<line x1="{left}" y1="{top}" x2="{left}" y2="{bottom}" stroke="black"/>"#,
+            r#"<text x="{mid_x}" y="{x_label_y}" text-anchor="middle" font-size="12">{x_label}</text>"#,
+            r#"<text x="15" y="{mid_y}" text-anchor="middle" font-size="12" transform="rotate(-90 15 {mid_y})">{y_label}</text>"#,
+        ),
+        mid_x = WIDTH / 2.0,
+        mid_y = HEIGHT / 2.0,
+        x_label_y = HEIGHT - 15.0,
+        left = left,
+        right = right,
+        top = top,
+        bottom = bottom,
+        x_label = x_label,
+        y_label = y_label,
+    );
+    for tick in 0..=4 {
+        let value = y_max * tick as f64 / 4.0;
+        let y = y_pos(value, y_max);
+        let _ = write!(
+            svg,
+            r#"<text x="{x}" y="{y:.1}" text-anchor="end" font-size="10">{value:.3}</text>"#,
+            x = left - 5.0,
+        );
+    }
+}
+
+fn legend(svg: &mut String, ind: usize, name: &str) {
+    let (x, y) = (WIDTH - MARGIN - 120.0, MARGIN + 15.0 * ind as f64);
+    let _ = write!(
+        svg,
+        concat!(
+            r#"<circle cx="{x}" cy="{cy}" r="4" fill="{color}"/>"#,
+            r#"<text x="{tx}" y="{ty}" font-size="12">{name}</text>"#,
+        ),
+        cy = y - 4.0,
+        tx = x + 8.0,
+        ty = y,
+        color = color(ind),
+        x = x,
+        name = name,
+    );
+}
@@ -0,0 +1,220 @@
+use super::errors::Error;
+use std::{env, process::Command, str};
+
+/// Checks whether an executable named `tool` can be found in `PATH`
+pub fn on_path(tool: &str) -> bool {
+    let Some(path) = env::var_os("PATH") else {
+        return false;
+    };
+    env::split_paths(&path).any(|dir| dir.join(tool).is_file())
+}
+
+/// Number of threads that can run in parallel, 1 if it cannot be determined
+pub fn available_parallelism() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+/// Version of the installed hyperfine, e.g. `1.18.0`
+pub fn hyperfine_version() -> Result<String, Error> {
+    let out = Command::new("hyperfine")
+        .arg("--version")
+        .output()
+        .map_err(|err| Error::external_tool("hyperfine", err))?;
+    // output has the form "hyperfine 1.18.0"
+    str::from_utf8(&out.stdout)
+        .unwrap_or("")
+        .split_whitespace()
+        .nth(1)
+        .map(|version| version.to_owned())
+        .ok_or(Error::external_tool("hyperfine", "Could not read version"))
+}
+
+/// Parses a version like `1.18.0` or `v1.18`, missing components are 0
+/// ```
+/// # use lib::tools::parse_version;
+/// assert_eq!(parse_version("1.18.0"), Some((1, 18, 0)));
+/// assert_eq!(parse_version("v1.14"), Some((1, 14, 0)));
+/// assert_eq!(parse_version("unknown"), None);
+/// ```
+pub fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = version
+        .trim()
+        .trim_start_matches('v')
+        .split('.')
+        .map(|part| {
+            // drop suffixes like `-rc1`
+            part.split(|c: char| !c.is_ascii_digit())
+                .next()
+                .and_then(|num| num.parse::<u32>().ok())
+        });
+    let major = parts.next()??;
+    let minor = parts.next().flatten().unwrap_or(0);
+    let patch = parts.next().flatten().unwrap_or(0);
+    Some((major, minor, patch))
+}
+
+/// Quotes `arg` for a POSIX shell, if it contains anything but safe characters
+/// ```
+/// # use lib::tools::shell_quote;
+/// assert_eq!(shell_quote("30"), "30");
+/// assert_eq!(shell_quote("my file.txt"), "'my file.txt'");
+/// assert_eq!(shell_quote("it's"), "'it'\\''s'");
+/// assert_eq!(shell_quote(""), "''");
+/// ```
+pub fn shell_quote(arg: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c);
+    if !arg.is_empty() && arg.chars().all(safe) {
+        return arg.to_owned();
+    }
+    format!("'{}'", arg.replace('\'', "'\\''"))
+}
+
+/// CPU the benchmarks ran on, stored in the result metadata
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct ProcessorInfo {
+    pub model_name: String,
+    pub physical_cores: u32,
+    pub logical_threads: u32,
+    pub base_freq_mhz: Option<u32>,
+    pub cache_l3_kb: Option<u32>,
+}
+
+impl ProcessorInfo {
+    /// Reads the processor of this machine from `/proc/cpuinfo` and sysfs on Linux,
+    /// `sysctl` on macOS and `wmic` on Windows
+    #[cfg(target_os = "linux")]
+    pub fn detect() -> ProcessorInfo {
+        let mut info = ProcessorInfo::from_cpuinfo(
+            &std::fs::read_to_string("/proc/cpuinfo").unwrap_or_default(),
+        );
+        let cpu_dir = std::path::Path::new("/sys/devices/system/cpu/cpu0");
+        // only exposed by intel_pstate, in kHz
+        info.base_freq_mhz = std::fs::read_to_string(cpu_dir.join("cpufreq/base_frequency"))
+            .ok()
+            .and_then(|khz| khz.trim().parse::<u32>().ok())
+            .map(|khz| khz / 1000);
+        // e.g. "32768K"
+        let l3_size = std::fs::read_to_string(cpu_dir.join("cache/index3/size"))
+            .ok()
+            .and_then(|size| size.trim().trim_end_matches('K').parse::<u32>().ok());
+        info.cache_l3_kb = l3_size.or(info.cache_l3_kb);
+        info
+    }
+
+    #[cfg(target_os = "macos")]
+    pub fn detect() -> ProcessorInfo {
+        let number = |name: &str| sysctl(name).and_then(|value| value.parse::<u64>().ok());
+        ProcessorInfo {
+            model_name: sysctl("machdep.cpu.brand_string").unwrap_or("unknown".to_owned()),
+            physical_cores: number("hw.physicalcpu").unwrap_or(0) as u32,
+            logical_threads: number("hw.logicalcpu").unwrap_or(0) as u32,
+            // not available on Apple Silicon
+            base_freq_mhz: number("hw.cpufrequency").map(|hz| (hz / 1_000_000) as u32),
+            cache_l3_kb: number("hw.l3cachesize").map(|bytes| (bytes / 1024) as u32),
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    pub fn detect() -> ProcessorInfo {
+        let stdout = Command::new("wmic")
+            .args([
+                "cpu",
+                "get",
+                "Name,NumberOfCores,NumberOfLogicalProcessors,MaxClockSpeed,L3CacheSize",
+                "/format:list",
+            ])
+            .output()
+            .map(|out| String::from_utf8_lossy(&out.stdout).into_owned())
+            .unwrap_or_default();
+        ProcessorInfo::from_wmic_output(&stdout)
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+    pub fn detect() -> ProcessorInfo {
+        ProcessorInfo {
+            model_name: "unknown".to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Parses the contents of `/proc/cpuinfo`
+    /// ```
+    /// # use lib::tools::ProcessorInfo;
+    /// let cpuinfo = "processor\t: 0\nmodel name\t: AMD Ryzen 7\nphysical id\t: 0\ncpu cores\t: 2\n\n\
+    ///                processor\t: 1\nmodel name\t: AMD Ryzen 7\nphysical id\t: 0\ncpu cores\t: 2\n\n\
+    ///                processor\t: 2\nmodel name\t: AMD Ryzen 7\nphysical id\t: 0\ncpu cores\t: 2\n\
+    ///                cache size\t: 16384 KB\n";
+    /// let info = ProcessorInfo::from_cpuinfo(cpuinfo);
+    /// assert_eq!(info.model_name, "AMD Ryzen 7");
+    /// assert_eq!((info.physical_cores, info.logical_threads), (2, 3));
+    /// assert_eq!(info.cache_l3_kb, Some(16384));
+    /// ```
+    pub fn from_cpuinfo(contents: &str) -> ProcessorInfo {
+        let fields = || {
+            contents
+                .lines()
+                .filter_map(|line| line.split_once(':'))
+                .map(|(key, value)| (key.trim(), value.trim()))
+        };
+        let field = |name: &str| {
+            fields()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value)
+        };
+
+        let logical_threads = fields().filter(|(key, _)| *key == "processor").count() as u32;
+        let mut sockets: Vec<&str> = fields()
+            .filter(|(key, _)| *key == "physical id")
+            .map(|(_, id)| id)
+            .collect();
+        sockets.sort();
+        sockets.dedup();
+        let physical_cores = field("cpu cores")
+            .and_then(|cores| cores.parse::<u32>().ok())
+            .map_or(logical_threads, |cores| cores * sockets.len().max(1) as u32);
+
+        ProcessorInfo {
+            // some ARM kernels only report the "Hardware" of the processor
+            model_name: field("model name")
+                .or(field("Hardware"))
+                .unwrap_or("unknown")
+                .to_owned(),
+            physical_cores,
+            logical_threads,
+            base_freq_mhz: None,
+            // on x86 this is the size of the last level cache
+            cache_l3_kb: field("cache size")
+                .and_then(|size| size.trim_end_matches("KB").trim().parse().ok()),
+        }
+    }
+
+    /// Parses the output of `wmic cpu get <fields> /format:list`
+    pub fn from_wmic_output(stdout: &str) -> ProcessorInfo {
+        let field = |name: &str| {
+            stdout
+                .lines()
+                .filter_map(|line| line.split_once('='))
+                .find(|(key, _)| key.trim() == name)
+                .map(|(_, value)| value.trim())
+        };
+        let number = |name: &str| field(name).and_then(|value| value.parse::<u32>().ok());
+        ProcessorInfo {
+            model_name: field("Name").unwrap_or("unknown").to_owned(),
+            physical_cores: number("NumberOfCores").unwrap_or(0),
+            logical_threads: number("NumberOfLogicalProcessors").unwrap_or(0),
+            base_freq_mhz: number("MaxClockSpeed"),
+            cache_l3_kb: number("L3CacheSize"),
+        }
+    }
+}
+
+/// Output of `sysctl -n <name>`
+#[cfg(target_os = "macos")]
+fn sysctl(name: &str) -> Option<String> {
+    let out = Command::new("sysctl").args(["-n", name]).output().ok()?;
+    out.status
+        .success()
+        .then(|| str::from_utf8(&out.stdout).unwrap_or("").trim().to_owned())
+}
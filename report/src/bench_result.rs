@@ -11,6 +11,8 @@ pub struct BenchResult {
 #[derive(Debug)]
 pub struct BenchData {
     pub lang: BenchmarkLanguage,
+    /// Label of the scenario the run belongs to, for benchmarks with scenarios
+    pub scenario: Option<String>,
     pub mean: f64,
     pub log_speedup: f64,
 }
@@ -24,7 +26,14 @@ impl BenchResult {
             let bench_name = bench
                 .map_err(|_| Error::path_access(&dir_path, "Read Dir Name"))?
                 .path();
-            results.push(BenchResult::new(&bench_name)?)
+            let is_sidecar = bench_name
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_none_or(Self::is_sidecar);
+            if is_sidecar || bench_name.extension().is_none_or(|ext| ext != "csv") {
+                continue;
+            }
+            results.extend(BenchResult::load_csv(&bench_name)?)
         }
 
         let avg = Self::get_geometric_mean(&results, false);
@@ -79,6 +88,7 @@ impl BenchResult {
                 / lang_results.len() as f64;
             avg_data.push(BenchData {
                 lang,
+                scenario: None,
                 mean: lang_mean,
                 log_speedup: lang_log_speedup,
             });
@@ -91,7 +101,8 @@ impl BenchResult {
         }
     }
 
-    pub fn new(path: &PathBuf) -> Result<BenchResult, Error> {
+    /// One result per scenario, or a single one if the benchmark has none
+    pub fn load_csv(path: &PathBuf) -> Result<Vec<BenchResult>, Error> {
         let name = path
             .file_stem()
             .ok_or(Error::path_access(path, "Read CSV file name"))?
@@ -112,51 +123,113 @@ impl BenchResult {
                 Some(command) => command,
             };
 
-            let bin_name = command
-                .split_terminator("/")
-                .nth(3)
-                .ok_or(Error::wrong_format_command(command))?;
-            let lang = match bin_name
-                .split(" ")
-                .next()
-                .ok_or(Error::wrong_format_command(command))?
-                .split_once("_")
-            {
-                None => BenchmarkLanguage::Scc,
-                Some((_, suffix)) => BenchmarkLanguage::from_suffix(suffix)?,
+            // interpreted languages are run directly from their source file
+            let source_lang = command
+                .split_whitespace()
+                .filter(|arg| arg.contains('/'))
+                .filter_map(|arg| arg.rsplit_once('.'))
+                .find_map(|(_, ext)| BenchmarkLanguage::from_ext(ext));
+            let (lang, scenario) = match Self::lang_from_name(command) {
+                Some(named) => named,
+                None => match source_lang {
+                    Some(lang) => (lang, None),
+                    None => (Self::lang_from_bin(command)?, None),
+                },
             };
 
-            data_all.push(BenchData::new(data, lang, path)?);
+            data_all.push(BenchData::new(data, lang, scenario, path)?);
         }
 
-        let index_scc = data_all
-            .iter()
-            .enumerate()
-            .find(|(_, datum)| datum.lang == BenchmarkLanguage::Scc)
-            .ok_or(Error::missing_lang(BenchmarkLanguage::Scc))?
-            .0;
-        let baseline = data_all.remove(index_scc);
-        let mut data: Vec<BenchData> = data_all
-            .into_iter()
-            .map(|datum| datum.to_relative(&baseline))
-            .collect();
+        let mut scenarios: Vec<Option<String>> = vec![];
+        for datum in data_all.iter() {
+            if !scenarios.contains(&datum.scenario) {
+                scenarios.push(datum.scenario.clone());
+            }
+        }
 
-        for lang in BenchmarkLanguage::all() {
-            if lang == BenchmarkLanguage::Scc
-                || data.iter().find(|datum| datum.lang == lang).is_some()
-            {
-                continue;
+        let mut results = vec![];
+        for scenario in scenarios {
+            let (mut scenario_data, rest): (Vec<BenchData>, Vec<BenchData>) = data_all
+                .into_iter()
+                .partition(|datum| datum.scenario == scenario);
+            data_all = rest;
+
+            // every scenario is compared against its own scc run
+            let index_scc = scenario_data
+                .iter()
+                .enumerate()
+                .find(|(_, datum)| datum.lang == BenchmarkLanguage::Scc)
+                .ok_or(Error::missing_lang(BenchmarkLanguage::Scc))?
+                .0;
+            let baseline = scenario_data.remove(index_scc);
+            let mut data: Vec<BenchData> = scenario_data
+                .into_iter()
+                .map(|datum| datum.to_relative(&baseline))
+                .collect();
+
+            for lang in BenchmarkLanguage::all() {
+                if lang == BenchmarkLanguage::Scc
+                    || data.iter().find(|datum| datum.lang == lang).is_some()
+                {
+                    continue;
+                }
+
+                data.push(BenchData::empty(&lang));
             }
 
-            data.push(BenchData::empty(&lang));
+            let benchmark = match &scenario {
+                Some(label) => format!("{name} ({label})"),
+                None => name.to_owned(),
+            };
+            results.push(BenchResult { benchmark, data });
         }
+        Ok(results)
+    }
 
-        Ok(BenchResult {
-            benchmark: name.to_owned(),
-            data,
+    /// Sidecar files are named `<benchmark>_<kind>`, archived results `<benchmark>_<timestamp>`
+    /// Benchmark names may contain `_` themselves, e.g. when they have a prefix
+    fn is_sidecar(stem: &str) -> bool {
+        const KINDS: [&str; 8] = [
+            "latest", "failed", "perf", "ci", "columns", "meta", "input", "commands",
+        ];
+        stem.rsplit_once('_').is_some_and(|(_, kind)| {
+            KINDS.contains(&kind) || kind.chars().all(|c| c.is_ascii_digit())
         })
     }
 
+    /// Commands of benchmarks with scenarios are named `<suffix>/<label>`, commands run
+    /// from a script (when the command line gets too long) just `<suffix>`
+    fn lang_from_name(command: &str) -> Option<(BenchmarkLanguage, Option<String>)> {
+        if command.starts_with('.') || command.contains(char::is_whitespace) {
+            return None;
+        }
+        let (suffix, label) = match command.split_once('/') {
+            Some((suffix, label)) => (suffix, Some(label.to_owned())),
+            None => (command, None),
+        };
+        let lang = BenchmarkLanguage::from_suffix(suffix).ok()?;
+        Some((lang, label))
+    }
+
+    fn lang_from_bin(command: &str) -> Result<BenchmarkLanguage, Error> {
+        let bin_name = command
+            .split_terminator("/")
+            .nth(3)
+            .ok_or(Error::wrong_format_command(command))?;
+        // binaries are named `<benchmark>_<suffix>`, where the benchmark name may contain
+        // `_` itself, scc binaries are named just `<benchmark>`
+        let suffix = bin_name
+            .split(" ")
+            .next()
+            .ok_or(Error::wrong_format_command(command))?
+            .rsplit_once("_")
+            // binaries like `Fib_grain.wasm` have an extension after the suffix
+            .map(|(_, suffix)| suffix.split('.').next().unwrap_or(suffix));
+        Ok(suffix
+            .and_then(|suffix| BenchmarkLanguage::from_suffix(suffix).ok())
+            .unwrap_or(BenchmarkLanguage::Scc))
+    }
+
     pub fn get_min_max(benches: &[Self]) -> (f64, f64) {
         let y_max = benches
             .iter()
@@ -199,6 +272,7 @@ impl BenchData {
     pub fn new<'a>(
         mut data: impl Iterator<Item = &'a str>,
         lang: BenchmarkLanguage,
+        scenario: Option<String>,
         path: &PathBuf,
     ) -> Result<BenchData, Error> {
         let mean_str = data.next().ok_or(Error::csv(&path, "Missing mean"))?;
@@ -207,6 +281,7 @@ impl BenchData {
             .map_err(|_| Error::ParseFloat(mean_str.to_owned()))?;
         Ok(BenchData {
             lang,
+            scenario,
             mean,
             log_speedup: 0.0,
         })
@@ -222,6 +297,7 @@ impl BenchData {
     pub fn empty(lang: &BenchmarkLanguage) -> BenchData {
         BenchData {
             lang: *lang,
+            scenario: None,
             mean: f64::NAN,
             log_speedup: f64::NAN,
         }
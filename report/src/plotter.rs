@@ -30,6 +30,16 @@ fn lang_color(lang: &BenchmarkLanguage) -> RGBColor {
         BenchmarkLanguage::SmlNj => RGBColor(143, 143, 143),
         BenchmarkLanguage::SmlMlton => RGBColor(37, 177, 228),
         BenchmarkLanguage::MoonBit => RGBColor(170, 51, 127),
+        BenchmarkLanguage::Gleam => RGBColor(255, 175, 243),
+        BenchmarkLanguage::Grain => RGBColor(255, 98, 15),
+        BenchmarkLanguage::Fennel => RGBColor(80, 150, 60),
+        BenchmarkLanguage::Unison => RGBColor(90, 60, 160),
+        BenchmarkLanguage::Verse => RGBColor(30, 110, 200),
+        BenchmarkLanguage::Ante => RGBColor(200, 120, 40),
+        BenchmarkLanguage::Beef => RGBColor(150, 70, 50),
+        BenchmarkLanguage::Lobster => RGBColor(220, 60, 60),
+        BenchmarkLanguage::Mojo => RGBColor(255, 80, 20),
+        BenchmarkLanguage::Carbon => RGBColor(60, 60, 60),
     }
 }
 
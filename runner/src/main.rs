@@ -1,6 +1,5 @@
 use clap::Parser;
-use lib::{benchmark::Benchmark, errors::Error, langs::BenchmarkLanguage};
-use std::str;
+use lib::{benchmark::Benchmark, errors::Error, filter::BenchmarkFilter, langs::BenchmarkLanguage};
 
 #[derive(clap::Parser)]
 pub struct Args {
@@ -21,6 +20,15 @@ pub struct Args {
     /// Optional: Exclude benchmark
     #[arg(long)]
     exclude_bench: Vec<String>,
+    /// Optional: Only run benchmarks with this tag
+    #[arg(long)]
+    tag: Vec<String>,
+    /// Optional: Exclude benchmarks with this tag
+    #[arg(long)]
+    exclude_tag: Vec<String>,
+    /// Optional: Abort on the first failing language
+    #[arg(long)]
+    strict: bool,
 }
 
 fn run() -> Result<(), Error> {
@@ -29,23 +37,36 @@ fn run() -> Result<(), Error> {
     if let Some(name) = args.name {
         benchmarks = vec![Benchmark::new(&name, &args.exclude_language)?];
     } else {
-        benchmarks = Benchmark::load_all(&args.exclude_language, &args.exclude_bench)?;
+        let mut filter = BenchmarkFilter::excluding(&args.exclude_language, &args.exclude_bench);
+        filter.include_tags = args.tag;
+        filter.exclude_tags = args.exclude_tag;
+        benchmarks = Benchmark::load_filtered(&filter)?;
     }
 
-    for benchmark in benchmarks {
+    Benchmark::validate_all(&benchmarks)?;
+
+    for mut benchmark in benchmarks {
         if args.skip_existing && benchmark.results_exist()? {
             continue;
         }
-        benchmark.compile_all()?;
+        benchmark.config.strict |= args.strict;
+        for output in benchmark.compile_all()? {
+            for warning in output.warnings {
+                println!("{} ({}): {warning}", benchmark.name, output.lang);
+            }
+        }
         if args.exec {
             let out = benchmark.run_all(false)?;
-            for output in out {
-                println!(
-                    "{}",
-                    str::from_utf8(&output.stdout).expect("Could not read stdout")
-                );
+            for outcome in out.iter() {
+                println!("{}", outcome.stdout_str());
+            }
+            if let Err(err) = benchmark.first_failure(&out) {
+                println!("{err}");
             }
         } else {
+            if let Ok(estimate) = benchmark.estimate_total_run_time() {
+                println!("{} will take about {estimate}", benchmark.name);
+            }
             benchmark.run_hyperfine_all()?;
         }
     }
@@ -53,6 +74,8 @@ fn run() -> Result<(), Error> {
 }
 
 fn main() {
+    // the library reports problems with the machine setup as warnings
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
     match run() {
         Ok(_) => (),
         Err(err) => println!("Runner exited with error:\n{err}"),